@@ -0,0 +1,117 @@
+#!/usr/bin/env python3
+"""Regenerate tests/fixtures/signing_vectors.json with the Hyperliquid Python SDK.
+
+    pip install hyperliquid-python-sdk
+    python3 scripts/signing_vectors.py
+
+Every case in scripts/signing_cases.json is signed for mainnet and testnet
+with the SDK's own signing functions. L1 actions also record the msgpack
+bytes and the connection id from `action_hash`. User-signed actions the SDK
+has no signing types for are skipped with a warning, so every vector written
+was produced by the SDK.
+"""
+
+import json
+import sys
+from pathlib import Path
+
+import eth_account
+import msgpack
+from hyperliquid.utils import signing
+
+ROOT = Path(__file__).resolve().parent.parent
+CASES = ROOT / "scripts" / "signing_cases.json"
+OUTPUT = ROOT / "tests" / "fixtures" / "signing_vectors.json"
+
+# Signing types and primary type of the user-signed actions, taken from the SDK.
+USER_SIGNED = {
+    "usdSend": ("USD_SEND_SIGN_TYPES", "HyperliquidTransaction:UsdSend"),
+    "withdraw3": ("WITHDRAW_SIGN_TYPES", "HyperliquidTransaction:Withdraw"),
+    "spotSend": ("SPOT_TRANSFER_SIGN_TYPES", "HyperliquidTransaction:SpotSend"),
+    "usdClassTransfer": ("USD_CLASS_TRANSFER_SIGN_TYPES", "HyperliquidTransaction:UsdClassTransfer"),
+    "sendAsset": ("SEND_ASSET_SIGN_TYPES", "HyperliquidTransaction:SendAsset"),
+    "tokenDelegate": ("TOKEN_DELEGATE_TYPES", "HyperliquidTransaction:TokenDelegate"),
+    "convertToMultiSigUser": (
+        "CONVERT_TO_MULTI_SIG_USER_SIGN_TYPES",
+        "HyperliquidTransaction:ConvertToMultiSigUser",
+    ),
+}
+
+# User-signed actions the SDK signs through a helper with inline types.
+HELPERS = {
+    "approveAgent": "sign_agent",
+    "approveBuilderFee": "sign_approve_builder_fee",
+}
+
+
+def signature_hex(signature):
+    r = signature["r"][2:].zfill(64)
+    s = signature["s"][2:].zfill(64)
+    return f"0x{r}{s}{signature['v']:02x}"
+
+
+def sign_user_signed(wallet, action, is_mainnet):
+    kind = action["type"]
+    if kind in HELPERS:
+        return getattr(signing, HELPERS[kind])(wallet, action, is_mainnet)
+    types, primary_type = USER_SIGNED.get(kind, (None, None))
+    if types is None or not hasattr(signing, types):
+        return None
+    return signing.sign_user_signed_action(
+        wallet, action, getattr(signing, types), primary_type, is_mainnet
+    )
+
+
+def vector(wallet, case, is_mainnet):
+    # The signing helpers set signatureChainId and hyperliquidChain in place.
+    action = json.loads(json.dumps(case["action"]))
+    nonce = case["nonce"]
+    vault = case["vaultAddress"]
+    network = "mainnet" if is_mainnet else "testnet"
+    result = {
+        "name": f"{case['name']}/{network}",
+        "nonce": nonce,
+        "vaultAddress": vault,
+        "isMainnet": is_mainnet,
+        "action": action,
+    }
+
+    if "hyperliquidChain" in action:
+        signature = sign_user_signed(wallet, action, is_mainnet)
+        if signature is None:
+            print(f"skipping {result['name']}: no SDK signing types", file=sys.stderr)
+            return None
+        result["signature"] = signature_hex(signature)
+        return result
+
+    if action["type"] == "multiSig":
+        # The multi-sig hash is made over the action without its type.
+        hashed = {key: value for key, value in action.items() if key != "type"}
+        signature = signing.sign_multi_sig_action(wallet, action, is_mainnet, vault, nonce, None)
+    else:
+        hashed = action
+        signature = signing.sign_l1_action(wallet, action, vault, nonce, None, is_mainnet)
+    result["msgpack"] = msgpack.packb(hashed).hex()
+    result["connectionId"] = "0x" + signing.action_hash(hashed, vault, nonce, None).hex()
+    result["signature"] = signature_hex(signature)
+    return result
+
+
+def main():
+    cases = json.loads(CASES.read_text())
+    wallet = eth_account.Account.from_key(cases["privateKey"])
+    vectors = [
+        vector(wallet, case, is_mainnet)
+        for case in cases["cases"]
+        for is_mainnet in (True, False)
+    ]
+    output = {
+        "privateKey": cases["privateKey"],
+        "vectors": [v for v in vectors if v is not None],
+    }
+    OUTPUT.write_text(json.dumps(output, indent=2) + "\n")
+    print(f"wrote {len(output['vectors'])} vectors to {OUTPUT}")
+
+
+if __name__ == "__main__":
+    main()
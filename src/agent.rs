@@ -0,0 +1,342 @@
+//! Agent (API) wallet lifecycle.
+//!
+//! [`AgentManager`] generates agent keys, approves them with the master key
+//! through `ApproveAgent`, persists them in an [`AgentStore`] and rotates
+//! them before their `valid_until` expiry. Approving a new agent under the
+//! name of an existing one replaces it on the exchange.
+
+use std::{collections::HashMap, fmt, future::Future, path::PathBuf, sync::Mutex, time::Duration};
+
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    Error, Result,
+    consts::SIGNATURE_CHAIN_ID,
+    exchange::{
+        ActionKind, ExchangeClient, builder::BuildAction, requests::ApproveAgent,
+        responses::ExchangeResponseStatus,
+    },
+    info::{ExtraAgent, HyperliquidInfoClient},
+    utils::{next_nonce, now_timestamp_ms},
+};
+
+/// Agent key as kept by an [`AgentStore`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredAgent {
+    pub name: String,
+    pub address: Address,
+    /// Hex encoded private key, zeroized on drop.
+    pub private_key: Zeroizing<String>,
+    pub valid_until: Option<u64>,
+}
+
+impl StoredAgent {
+    pub fn signer(&self) -> Result<PrivateKeySigner> {
+        self.private_key
+            .parse()
+            .map_err(|e: alloy::signers::local::LocalSignerError| {
+                Error::PrivateKeyParse(e.to_string())
+            })
+    }
+}
+
+impl fmt::Debug for StoredAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredAgent")
+            .field("name", &self.name)
+            .field("address", &self.address)
+            .field("valid_until", &self.valid_until)
+            .finish_non_exhaustive()
+    }
+}
+
+pub trait AgentStore {
+    fn save(&self, agent: &StoredAgent) -> impl Future<Output = Result<()>> + Send;
+
+    fn load(&self, name: &str) -> impl Future<Output = Result<Option<StoredAgent>>> + Send;
+}
+
+/// Keeps agents for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemoryAgentStore {
+    agents: Mutex<HashMap<String, StoredAgent>>,
+}
+
+impl AgentStore for MemoryAgentStore {
+    async fn save(&self, agent: &StoredAgent) -> Result<()> {
+        self.agents
+            .lock()
+            .unwrap()
+            .insert(agent.name.clone(), agent.clone());
+        Ok(())
+    }
+
+    async fn load(&self, name: &str) -> Result<Option<StoredAgent>> {
+        Ok(self.agents.lock().unwrap().get(name).cloned())
+    }
+}
+
+/// Keeps agents in a JSON file keyed by name, readable only by its owner on
+/// unix. Keys are stored unencrypted.
+#[derive(Debug, Clone)]
+pub struct FileAgentStore {
+    pub path: PathBuf,
+}
+
+impl FileAgentStore {
+    fn read(&self) -> Result<HashMap<String, StoredAgent>> {
+        match std::fs::read_to_string(&self.path).map(Zeroizing::new) {
+            Ok(data) => serde_json::from_str(&data).map_err(|e| Error::JsonParse(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(Error::GenericReader(e.to_string())),
+        }
+    }
+
+    fn write(&self, agents: &HashMap<String, StoredAgent>) -> Result<()> {
+        let data = Zeroizing::new(
+            serde_json::to_string_pretty(agents).map_err(|e| Error::JsonParse(e.to_string()))?,
+        );
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options
+            .open(&self.path)
+            .map_err(|e| Error::GenericReader(e.to_string()))?;
+        // The mode only applies to a new file.
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))
+            .map_err(|e| Error::GenericReader(e.to_string()))?;
+        std::io::Write::write_all(&mut file, data.as_bytes())
+            .map_err(|e| Error::GenericReader(e.to_string()))
+    }
+}
+
+impl AgentStore for FileAgentStore {
+    async fn save(&self, agent: &StoredAgent) -> Result<()> {
+        let mut agents = self.read()?;
+        agents.insert(agent.name.clone(), agent.clone());
+        self.write(&agents)
+    }
+
+    async fn load(&self, name: &str) -> Result<Option<StoredAgent>> {
+        Ok(self.read()?.remove(name))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentManagerConfig {
+    /// Lifetime of new agents, `None` for agents that never expire.
+    pub validity: Option<Duration>,
+    /// Agents expiring within this window are rotated.
+    pub rotate_before: Duration,
+}
+
+impl Default for AgentManagerConfig {
+    fn default() -> Self {
+        Self {
+            validity: Some(Duration::from_secs(30 * 24 * 60 * 60)),
+            rotate_before: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+pub struct AgentManager<S> {
+    pub client: ExchangeClient,
+    pub info_client: HyperliquidInfoClient,
+    /// Key of the account the agents trade for.
+    pub master: PrivateKeySigner,
+    pub store: S,
+    pub config: AgentManagerConfig,
+}
+
+impl<S: AgentStore> AgentManager<S> {
+    /// Fresh agent key and the `ApproveAgent` action approving it as `name`,
+    /// valid from `now_ms`.
+    pub fn new_agent(&self, name: &str, now_ms: u64) -> (PrivateKeySigner, ApproveAgent) {
+        let agent = PrivateKeySigner::random();
+        let approve_agent = ApproveAgent {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            hyperliquid_chain: self.client.hyperliquid_chain().to_string(),
+            agent_address: agent.address(),
+            agent_name: Some(agent_name(name, self.valid_until(now_ms))),
+            nonce: next_nonce(),
+        };
+        (agent, approve_agent)
+    }
+
+    fn valid_until(&self, now_ms: u64) -> Option<u64> {
+        self.config
+            .validity
+            .map(|validity| now_ms + validity.as_millis() as u64)
+    }
+
+    /// Generate an agent, approve it with the master key and store it.
+    pub async fn create_agent(&self, name: &str) -> Result<StoredAgent> {
+        let now_ms = now_timestamp_ms();
+        let (agent, approve_agent) = self.new_agent(name, now_ms);
+
+        let status = ActionKind::ApproveAgent(approve_agent)
+            .build(&self.client)?
+            .sign(&self.master)?
+            .send()
+            .await?;
+        if let ExchangeResponseStatus::Err(err) = status {
+            return Err(Error::GenericRequest(err));
+        }
+
+        let stored = StoredAgent {
+            name: name.to_string(),
+            address: agent.address(),
+            private_key: Zeroizing::new(agent.to_bytes().to_string()),
+            valid_until: self.valid_until(now_ms),
+        };
+        self.store.save(&stored).await?;
+        info!("approved agent {name} at {}", stored.address);
+        Ok(stored)
+    }
+
+    /// Agents currently approved for the master account.
+    pub async fn agents(&self) -> Result<Vec<ExtraAgent>> {
+        self.info_client.extra_agents(self.master.address()).await
+    }
+
+    /// Replace the agents expiring within `rotate_before`, returning the new
+    /// ones. Only agents whose key is in the store are rotated; the others
+    /// are only warned about.
+    pub async fn rotate_expiring(&self) -> Result<Vec<StoredAgent>> {
+        let agents = self.agents().await?;
+        let mut rotated = vec![];
+
+        for agent in expiring(&agents, now_timestamp_ms(), self.config.rotate_before) {
+            let name = base_name(&agent.name);
+            warn!(
+                "agent {name} at {} expires at {:?}",
+                agent.address, agent.valid_until
+            );
+            match self.store.load(name).await? {
+                Some(stored) if stored.address == agent.address => {
+                    rotated.push(self.create_agent(name).await?);
+                }
+                _ => warn!("agent {name} is not in the store, not rotating it"),
+            }
+        }
+
+        Ok(rotated)
+    }
+}
+
+/// Name sent in `ApproveAgent`: the exchange reads the expiry from a
+/// `valid_until` suffix.
+fn agent_name(name: &str, valid_until: Option<u64>) -> String {
+    match valid_until {
+        Some(valid_until) => format!("{name} valid_until {valid_until}"),
+        None => name.to_string(),
+    }
+}
+
+fn base_name(name: &str) -> &str {
+    name.split_once(" valid_until ")
+        .map_or(name, |(name, _)| name)
+}
+
+fn expiring(agents: &[ExtraAgent], now_ms: u64, window: Duration) -> Vec<&ExtraAgent> {
+    let cutoff = now_ms + window.as_millis() as u64;
+    agents
+        .iter()
+        .filter(|agent| agent.valid_until.is_some_and(|until| until <= cutoff))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extra_agent(name: &str, valid_until: Option<u64>) -> ExtraAgent {
+        ExtraAgent {
+            name: name.to_string(),
+            address: Address::repeat_byte(0x01),
+            valid_until,
+        }
+    }
+
+    #[test]
+    fn test_agent_name() {
+        assert_eq!(agent_name("bot", None), "bot");
+        assert_eq!(agent_name("bot", Some(1)), "bot valid_until 1");
+        assert_eq!(base_name("bot valid_until 1"), "bot");
+        assert_eq!(base_name("bot"), "bot");
+    }
+
+    #[test]
+    fn test_new_agent() -> Result<()> {
+        let manager = AgentManager {
+            client: ExchangeClient::new(Some(crate::BaseUrl::Testnet), None, HashMap::new())?,
+            info_client: HyperliquidInfoClient::new(Some(crate::BaseUrl::Testnet))?,
+            master: PrivateKeySigner::random(),
+            store: MemoryAgentStore::default(),
+            config: AgentManagerConfig::default(),
+        };
+
+        let (agent, approve_agent) = manager.new_agent("bot", 1_000);
+        assert_eq!(approve_agent.agent_address, agent.address());
+        assert_eq!(approve_agent.hyperliquid_chain, "Testnet");
+        assert_eq!(
+            approve_agent.agent_name.as_deref(),
+            Some("bot valid_until 2592001000")
+        );
+        // Agents made in the same millisecond still get distinct nonces.
+        assert_ne!(manager.new_agent("bot", 1_000).1.nonce, approve_agent.nonce);
+        Ok(())
+    }
+
+    #[test]
+    fn test_expiring() {
+        let agents = [
+            extra_agent("soon", Some(1_000 + 3_600_000)),
+            extra_agent("later", Some(1_000 + 3 * 24 * 3_600_000)),
+            extra_agent("never", None),
+        ];
+        let names: Vec<_> = expiring(&agents, 1_000, Duration::from_secs(24 * 3600))
+            .into_iter()
+            .map(|agent| agent.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["soon"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_agent_store() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("hl-rs-agents-{}.json", now_timestamp_ms()));
+        let store = FileAgentStore { path: path.clone() };
+        let agent = PrivateKeySigner::random();
+        let stored = StoredAgent {
+            name: "bot".to_string(),
+            address: agent.address(),
+            private_key: Zeroizing::new(agent.to_bytes().to_string()),
+            valid_until: Some(1),
+        };
+
+        assert!(store.load("bot").await?.is_none());
+        // A file that already exists is made owner-only too.
+        std::fs::write(&path, "{}").unwrap();
+        store.save(&stored).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let loaded = store.load("bot").await?.unwrap();
+        std::fs::remove_file(path).ok();
+
+        assert_eq!(loaded.signer()?.address(), agent.address());
+        assert!(!format!("{loaded:?}").contains(loaded.private_key.as_str()));
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! `hl-signer`, a signing service that keeps the key out of strategy
+//! processes.
+//!
+//! It listens on localhost and signs actions posted by
+//! `hl_rs::signer::RemoteSigner` when they pass the policy file. The key is
+//! read from `--keystore`, then from the mnemonic in `HL_MNEMONIC`, then from
+//! `HL_PRIVATE_KEY`. Clients must send `HL_SIGNER_TOKEN` as a bearer token
+//! when it is set.
+
+use std::{env, net::SocketAddr, path::PathBuf};
+
+use alloy::signers::local::PrivateKeySigner;
+use clap::{Parser, ValueEnum};
+use hl_rs::{
+    Result,
+    keys::{self, DEFAULT_DERIVATION_PATH, Password},
+    signer::{Policy, SignerConfig, SignerServer},
+};
+use log::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Network {
+    Mainnet,
+    Testnet,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "hl-signer",
+    version,
+    about = "Policy-enforcing Hyperliquid signer"
+)]
+struct Args {
+    /// JSON policy listing what may be signed.
+    #[arg(long, env = "HL_SIGNER_POLICY")]
+    policy: PathBuf,
+    #[arg(long, env = "HL_SIGNER_LISTEN", default_value = "127.0.0.1:7575")]
+    listen: SocketAddr,
+    #[arg(long, env = "HL_NETWORK", value_enum, default_value_t = Network::Testnet)]
+    network: Network,
+    /// Encrypted JSON keystore holding the key.
+    #[arg(long, env = "HL_KEYSTORE")]
+    keystore: Option<PathBuf>,
+    /// File whose first line is the keystore password, prompted otherwise.
+    #[arg(long, env = "HL_KEYSTORE_PASSWORD_FILE")]
+    password_file: Option<PathBuf>,
+    /// Derivation path of the key in HL_MNEMONIC.
+    #[arg(long, env = "HL_DERIVATION_PATH", default_value = DEFAULT_DERIVATION_PATH)]
+    derivation_path: String,
+}
+
+fn load_signer(args: &Args) -> Result<PrivateKeySigner> {
+    if let Some(keystore) = &args.keystore {
+        let password = match &args.password_file {
+            Some(path) => Password::File(path.clone()),
+            None => Password::Prompt,
+        };
+        return keys::from_keystore(keystore, &password);
+    }
+
+    if env::var_os("HL_MNEMONIC").is_some() {
+        return keys::mnemonic_from_env("HL_MNEMONIC", &args.derivation_path);
+    }
+    keys::from_env("HL_PRIVATE_KEY")
+}
+
+async fn run(args: Args) -> Result<()> {
+    let policy = Policy::from_file(&args.policy)?;
+    let wallet = load_signer(&args)?;
+    if !args.listen.ip().is_loopback() {
+        warn!(
+            "listening on {}, which is not a loopback address",
+            args.listen
+        );
+    }
+
+    let address = wallet.address();
+    let server = SignerServer::start(SignerConfig {
+        wallet,
+        policy,
+        is_mainnet: args.network == Network::Mainnet,
+        token: env::var("HL_SIGNER_TOKEN").ok(),
+        addr: args.listen,
+    })
+    .await?;
+    info!("signing for {address} on {}", server.url());
+
+    tokio::signal::ctrl_c().await.ok();
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    if let Err(err) = run(Args::parse()).await {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,31 @@
+use std::{io::Read, path::PathBuf};
+
+use clap::Args;
+use hl_rs::{Error, Result, exchange::decode_payload};
+
+use crate::{Context, Network};
+
+#[derive(Args)]
+pub struct DecodeArgs {
+    /// File holding the JSON payload, read from stdin when omitted.
+    file: Option<PathBuf>,
+}
+
+pub fn run(context: &Context, args: DecodeArgs) -> Result<()> {
+    let payload = match args.file {
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| Error::GenericReader(e.to_string()))?
+        }
+        None => {
+            let mut payload = String::new();
+            std::io::stdin()
+                .read_to_string(&mut payload)
+                .map_err(|e| Error::GenericReader(e.to_string()))?;
+            payload
+        }
+    };
+
+    let decoded = decode_payload(&payload, context.global.network == Network::Mainnet)?;
+    println!("{decoded}");
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use alloy::primitives::Address;
+use clap::{Args, ValueEnum};
+use hl_rs::{
+    Error, ExchangeClient, Result, SIGNATURE_CHAIN_ID,
+    exchange::{
+        ActionKind,
+        builder::BuildAction,
+        requests::{
+            ApproveAgent, BulkCancel, BulkCancelCloid, BulkModify, BulkOrder, ClientCancelRequest,
+            ClientCancelRequestCloid, ClientLimit, ClientOrder, ClientOrderRequest,
+            ClientUpdateLeverage, ModifyRequest, UsdSend, Withdraw3,
+        },
+        responses::{ExchangeDataStatus, ExchangeResponseData, ExchangeResponseStatus},
+    },
+    utils::next_nonce,
+};
+use uuid::Uuid;
+
+use crate::{Context, offline};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Tif {
+    /// Good til cancelled.
+    Gtc,
+    /// Immediate or cancel.
+    Ioc,
+    /// Add liquidity only.
+    Alo,
+}
+
+impl Tif {
+    fn as_str(self) -> &'static str {
+        match self {
+            Tif::Gtc => "Gtc",
+            Tif::Ioc => "Ioc",
+            Tif::Alo => "Alo",
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct OrderArgs {
+    /// Coin, as "BTC" or "dex:COIN" on builder-deployed perp dexs, or spot
+    /// pair, as "PURR/USDC" or "@107".
+    coin: String,
+    #[arg(value_enum)]
+    side: Side,
+    size: f64,
+    price: f64,
+    #[arg(long, value_enum, default_value_t = Tif::Gtc)]
+    tif: Tif,
+    #[arg(long)]
+    reduce_only: bool,
+    #[arg(long)]
+    cloid: Option<Uuid>,
+}
+
+impl OrderArgs {
+    fn into_request(self) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: self.coin,
+            is_buy: matches!(self.side, Side::Buy),
+            reduce_only: self.reduce_only,
+            limit_px: self.price,
+            sz: self.size,
+            cloid: self.cloid,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: self.tif.as_str().to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct CancelArgs {
+    coin: String,
+    #[arg(required_unless_present = "cloid", conflicts_with = "cloid")]
+    oid: Option<u64>,
+    #[arg(long)]
+    cloid: Option<Uuid>,
+}
+
+#[derive(Args)]
+pub struct ModifyArgs {
+    /// Oid of the order to replace.
+    oid: u64,
+    #[command(flatten)]
+    order: OrderArgs,
+}
+
+#[derive(Args)]
+pub struct LeverageArgs {
+    coin: String,
+    leverage: u32,
+    /// Use isolated instead of cross margin.
+    #[arg(long)]
+    isolated: bool,
+}
+
+#[derive(Args)]
+pub struct TransferArgs {
+    destination: Address,
+    /// Amount of USDC, as a decimal string.
+    amount: String,
+}
+
+#[derive(Args)]
+pub struct ApproveAgentArgs {
+    agent: Address,
+    /// Name of the agent. Unnamed agents replace the previous unnamed one.
+    #[arg(long)]
+    name: Option<String>,
+}
+
+/// Sign `action` with the key and send it through `client`, or write it to
+/// `--output`.
+async fn send(context: &Context, client: &ExchangeClient, action: ActionKind) -> Result<()> {
+    let wallet = context.signer()?;
+    let signed = action.build(client)?.sign(&wallet)?;
+    if let Some(path) = &context.global.output {
+        return offline::write_signed(&signed, path);
+    }
+
+    report(signed.send().await?, context.global.json)
+}
+
+/// Print `status`, failing when the exchange rejected the action or any
+/// order in it.
+pub fn report(status: ExchangeResponseStatus, json: bool) -> Result<()> {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status).map_err(|e| Error::JsonParse(e.to_string()))?
+        );
+    }
+
+    let response = match status {
+        ExchangeResponseStatus::Ok(response) => response,
+        ExchangeResponseStatus::Err(err) => return Err(Error::GenericRequest(err)),
+    };
+    let statuses = match response.data {
+        Some(ExchangeResponseData::Statuses(data)) => data.statuses,
+        _ => {
+            if !json {
+                println!("ok: {}", response.response_type);
+            }
+            return Ok(());
+        }
+    };
+
+    let mut errors = Vec::new();
+    for status in statuses {
+        let line = match status {
+            ExchangeDataStatus::Success => "ok".to_string(),
+            ExchangeDataStatus::WaitingForFill => "waiting for fill".to_string(),
+            ExchangeDataStatus::WaitingForTrigger => "waiting for trigger".to_string(),
+            ExchangeDataStatus::Resting(resting) => format!("resting: oid {}", resting.oid),
+            ExchangeDataStatus::Filled(filled) => format!(
+                "filled: oid {}, {} at {}",
+                filled.oid, filled.total_sz, filled.avg_px
+            ),
+            ExchangeDataStatus::Error(err) => {
+                errors.push(err.clone());
+                format!("error: {err}")
+            }
+        };
+        if !json {
+            println!("{line}");
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::GenericRequest(errors.join("; ")))
+    }
+}
+
+pub async fn order(context: &Context, args: OrderArgs) -> Result<()> {
+    let client = context.exchange_client().await?;
+    let order = args.into_request().convert(client.coin_to_asset())?;
+    let action = ActionKind::Order(BulkOrder {
+        orders: vec![order],
+        grouping: "na".to_string(),
+        builder: None,
+    });
+    send(context, &client, action).await
+}
+
+pub async fn cancel(context: &Context, args: CancelArgs) -> Result<()> {
+    let client = context.exchange_client().await?;
+    let action = match (args.oid, args.cloid) {
+        (_, Some(cloid)) => ActionKind::CancelByCloid(BulkCancelCloid {
+            cancels: vec![
+                ClientCancelRequestCloid {
+                    asset: args.coin,
+                    cloid,
+                }
+                .convert(client.coin_to_asset())?,
+            ],
+        }),
+        (Some(oid), None) => ActionKind::Cancel(BulkCancel {
+            cancels: vec![
+                ClientCancelRequest {
+                    asset: args.coin,
+                    oid,
+                }
+                .convert(client.coin_to_asset())?,
+            ],
+        }),
+        (None, None) => return Err(Error::GenericParse("pass an oid or --cloid".to_string())),
+    };
+    send(context, &client, action).await
+}
+
+pub async fn modify(context: &Context, args: ModifyArgs) -> Result<()> {
+    let client = context.exchange_client().await?;
+    let order = args.order.into_request().convert(client.coin_to_asset())?;
+    let action = ActionKind::BatchModify(BulkModify {
+        modifies: vec![ModifyRequest {
+            oid: args.oid,
+            order,
+        }],
+    });
+    send(context, &client, action).await
+}
+
+pub async fn leverage(context: &Context, args: LeverageArgs) -> Result<()> {
+    let client = context.exchange_client().await?;
+    let update_leverage = ClientUpdateLeverage {
+        asset: args.coin,
+        is_cross: !args.isolated,
+        leverage: args.leverage,
+    }
+    .convert(client.coin_to_asset())?;
+    send(
+        context,
+        &client,
+        ActionKind::UpdateLeverage(update_leverage),
+    )
+    .await
+}
+
+fn check_amount(amount: &str) -> Result<()> {
+    match amount.parse::<f64>() {
+        Ok(value) if value > 0.0 => Ok(()),
+        _ => Err(Error::GenericParse(format!("invalid amount {amount}"))),
+    }
+}
+
+pub async fn transfer(context: &Context, args: TransferArgs) -> Result<()> {
+    check_amount(&args.amount)?;
+    let client = context.exchange_client_without_assets()?;
+    let action = ActionKind::UsdSend(UsdSend {
+        signature_chain_id: SIGNATURE_CHAIN_ID,
+        hyperliquid_chain: client.hyperliquid_chain().to_string(),
+        destination: args.destination.to_string().to_lowercase(),
+        amount: args.amount,
+        time: next_nonce(),
+    });
+    send(context, &client, action).await
+}
+
+pub async fn withdraw(context: &Context, args: TransferArgs) -> Result<()> {
+    check_amount(&args.amount)?;
+    let client = context.exchange_client_without_assets()?;
+    let action = ActionKind::Withdraw3(Withdraw3 {
+        signature_chain_id: SIGNATURE_CHAIN_ID,
+        hyperliquid_chain: client.hyperliquid_chain().to_string(),
+        destination: args.destination.to_string().to_lowercase(),
+        amount: args.amount,
+        time: next_nonce(),
+    });
+    send(context, &client, action).await
+}
+
+pub async fn approve_agent(context: &Context, args: ApproveAgentArgs) -> Result<()> {
+    let client = context.exchange_client_without_assets()?;
+    let action = ActionKind::ApproveAgent(ApproveAgent {
+        signature_chain_id: SIGNATURE_CHAIN_ID,
+        hyperliquid_chain: client.hyperliquid_chain().to_string(),
+        agent_address: args.agent,
+        agent_name: args.name,
+        nonce: next_nonce(),
+    });
+    send(context, &client, action).await
+}
@@ -0,0 +1,158 @@
+use alloy::primitives::Address;
+use clap::Args;
+use hl_rs::{Error, Result, info::InfoRequest};
+use serde_json::Value;
+
+use crate::Context;
+
+#[derive(Args)]
+pub struct UserArgs {
+    /// Account to query, defaulting to --vault and then to the signing key's
+    /// address.
+    #[arg(long)]
+    user: Option<Address>,
+}
+
+#[derive(Args)]
+pub struct BookArgs {
+    coin: String,
+}
+
+impl UserArgs {
+    fn user(&self, context: &Context) -> Result<Address> {
+        match self.user.or(context.global.vault) {
+            Some(user) => Ok(user),
+            None => Ok(context.signer()?.address()),
+        }
+    }
+}
+
+/// Print the raw response to `request`.
+async fn print_json(context: &Context, request: &InfoRequest) -> Result<()> {
+    let response: Value = context.info_client()?.send_info_request(request).await?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&response).map_err(|e| Error::JsonParse(e.to_string()))?
+    );
+    Ok(())
+}
+
+pub async fn positions(context: &Context, args: UserArgs) -> Result<()> {
+    let user = args.user(context)?;
+    if context.global.json {
+        return print_json(context, &InfoRequest::ClearinghouseState { user }).await;
+    }
+
+    let state = context.info_client()?.user_state(user).await?;
+    println!("account value: {}", state.margin_summary.account_value);
+    println!("margin used:   {}", state.margin_summary.total_margin_used);
+    println!("withdrawable:  {}", state.withdrawable);
+    if state.asset_positions.is_empty() {
+        println!("no open positions");
+        return Ok(());
+    }
+
+    println!(
+        "\n{:<12} {:>14} {:>14} {:>14} {:>14}",
+        "coin", "size", "entry", "liquidation", "pnl"
+    );
+    for asset_position in state.asset_positions {
+        let position = asset_position.position;
+        println!(
+            "{:<12} {:>14} {:>14} {:>14} {:>14}",
+            position.coin,
+            position.szi,
+            position.entry_px.as_deref().unwrap_or("-"),
+            position.liquidation_px.as_deref().unwrap_or("-"),
+            position.unrealized_pnl,
+        );
+    }
+    Ok(())
+}
+
+pub async fn orders(context: &Context, args: UserArgs) -> Result<()> {
+    let user = args.user(context)?;
+    if context.global.json {
+        return print_json(context, &InfoRequest::OpenOrders { user }).await;
+    }
+
+    let orders = context.info_client()?.open_orders(user).await?;
+    if orders.is_empty() {
+        println!("no open orders");
+        return Ok(());
+    }
+
+    println!(
+        "{:<14} {:<12} {:<5} {:>14} {:>14}",
+        "oid", "coin", "side", "size", "price"
+    );
+    for order in orders {
+        println!(
+            "{:<14} {:<12} {:<5} {:>14} {:>14}",
+            order.oid,
+            order.coin,
+            side(&order.side),
+            order.sz,
+            order.limit_px,
+        );
+    }
+    Ok(())
+}
+
+pub async fn fills(context: &Context, args: UserArgs) -> Result<()> {
+    let user = args.user(context)?;
+    if context.global.json {
+        return print_json(context, &InfoRequest::UserFills { user }).await;
+    }
+
+    let fills = context.info_client()?.user_fills(user).await?;
+    if fills.is_empty() {
+        println!("no fills");
+        return Ok(());
+    }
+
+    println!(
+        "{:<14} {:<12} {:<16} {:>14} {:>14} {:>14}",
+        "time", "coin", "direction", "size", "price", "closed pnl"
+    );
+    for fill in fills {
+        println!(
+            "{:<14} {:<12} {:<16} {:>14} {:>14} {:>14}",
+            fill.time, fill.coin, fill.dir, fill.sz, fill.px, fill.closed_pnl,
+        );
+    }
+    Ok(())
+}
+
+pub async fn book(context: &Context, args: BookArgs) -> Result<()> {
+    if context.global.json {
+        return print_json(context, &InfoRequest::L2Book { coin: args.coin }).await;
+    }
+
+    let book = context.info_client()?.l2_snapshot(args.coin).await?;
+    let [bids, asks] = &book.levels[..] else {
+        return Err(Error::JsonParse(format!(
+            "expected 2 sides in the {} book",
+            book.coin
+        )));
+    };
+
+    println!(
+        "{:>14} {:>14} | {:<14} {:<14}",
+        "bid size", "bid", "ask", "ask size"
+    );
+    for i in 0..bids.len().max(asks.len()) {
+        let (bid_sz, bid_px) = bids.get(i).map_or(("", ""), |level| (&level.sz, &level.px));
+        let (ask_px, ask_sz) = asks.get(i).map_or(("", ""), |level| (&level.px, &level.sz));
+        println!("{bid_sz:>14} {bid_px:>14} | {ask_px:<14} {ask_sz:<14}");
+    }
+    Ok(())
+}
+
+fn side(side: &str) -> &str {
+    match side {
+        "B" => "buy",
+        "A" => "sell",
+        side => side,
+    }
+}
@@ -0,0 +1,28 @@
+use std::env;
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    Result,
+    keys::{self, Password},
+};
+
+use crate::GlobalArgs;
+
+/// Signer from `--keystore`, then `HL_MNEMONIC`, then `HL_PRIVATE_KEY`.
+pub fn load_signer(global: &GlobalArgs) -> Result<PrivateKeySigner> {
+    if let Some(keystore) = &global.keystore {
+        let password = match &global.password_file {
+            Some(path) => Password::File(path.clone()),
+            None if env::var_os("HL_KEYSTORE_PASSWORD").is_some() => {
+                Password::Env("HL_KEYSTORE_PASSWORD".to_string())
+            }
+            None => Password::Prompt,
+        };
+        return keys::from_keystore(keystore, &password);
+    }
+
+    if env::var_os("HL_MNEMONIC").is_some() {
+        return keys::mnemonic_from_env("HL_MNEMONIC", &global.derivation_path);
+    }
+    keys::from_env("HL_PRIVATE_KEY")
+}
@@ -0,0 +1,219 @@
+//! `hl`, a command line client for Hyperliquid.
+//!
+//! The signing key is read from the encrypted JSON keystore given with
+//! `--keystore`, whose password comes from `--password-file`,
+//! `HL_KEYSTORE_PASSWORD` or a prompt. Without a keystore it is derived from
+//! the mnemonic in `HL_MNEMONIC` at `--derivation-path`, or read from
+//! `HL_PRIVATE_KEY`.
+//!
+//! For cold keys, exchange commands run with `--output` and `--assets` sign
+//! without network access and write the `/exchange` body to a file, which
+//! `hl broadcast` sends from an online host.
+
+mod decode;
+mod exchange;
+mod info;
+mod keys;
+mod offline;
+
+use std::{collections::HashMap, path::PathBuf};
+
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use hl_rs::{BaseUrl, Error, ExchangeClient, Result, info::HyperliquidInfoClient};
+
+#[derive(Parser)]
+#[command(name = "hl", version, about = "Hyperliquid command line client")]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+pub struct GlobalArgs {
+    #[arg(
+        long,
+        global = true,
+        env = "HL_NETWORK",
+        value_enum,
+        default_value_t = Network::Testnet
+    )]
+    pub network: Network,
+    /// API URL to use instead of that of --network, e.g. a local mock. Not
+    /// allowed on mainnet.
+    #[arg(long, global = true, env = "HL_API_URL")]
+    pub base_url: Option<String>,
+    /// Vault or sub-account to trade for, and to query by default.
+    #[arg(long, global = true, env = "HL_VAULT_ADDRESS")]
+    pub vault: Option<Address>,
+    /// Encrypted JSON keystore holding the signing key, used instead of
+    /// HL_MNEMONIC and HL_PRIVATE_KEY.
+    #[arg(long, global = true, env = "HL_KEYSTORE")]
+    pub keystore: Option<PathBuf>,
+    /// File whose first line is the keystore password.
+    #[arg(long, global = true, env = "HL_KEYSTORE_PASSWORD_FILE")]
+    pub password_file: Option<PathBuf>,
+    /// Derivation path of the key in HL_MNEMONIC.
+    #[arg(
+        long,
+        global = true,
+        env = "HL_DERIVATION_PATH",
+        default_value = hl_rs::keys::DEFAULT_DERIVATION_PATH
+    )]
+    pub derivation_path: String,
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    pub json: bool,
+    /// Write the signed `/exchange` body of an exchange command to this
+    /// file instead of sending it.
+    #[arg(long, global = true)]
+    pub output: Option<PathBuf>,
+    /// Coin to asset map written by `hl assets`, used instead of fetching
+    /// it from the info endpoint.
+    #[arg(long, global = true, env = "HL_ASSETS")]
+    pub assets: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Localhost,
+}
+
+impl Network {
+    fn base_url(self) -> BaseUrl {
+        match self {
+            Network::Mainnet => BaseUrl::Mainnet,
+            Network::Testnet => BaseUrl::Testnet,
+            Network::Localhost => BaseUrl::Localhost,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Perp positions and margin summary.
+    Positions(info::UserArgs),
+    /// Resting orders.
+    Orders(info::UserArgs),
+    /// Most recent fills.
+    Fills(info::UserArgs),
+    /// Order book of a coin.
+    Book(info::BookArgs),
+    /// Place a limit order.
+    Order(exchange::OrderArgs),
+    /// Cancel an order by oid or cloid.
+    Cancel(exchange::CancelArgs),
+    /// Replace a resting order.
+    Modify(exchange::ModifyArgs),
+    /// Set the leverage of a coin.
+    Leverage(exchange::LeverageArgs),
+    /// Send USDC to another account.
+    Transfer(exchange::TransferArgs),
+    /// Withdraw USDC to Arbitrum.
+    Withdraw(exchange::TransferArgs),
+    /// Approve an agent wallet to trade for the account.
+    ApproveAgent(exchange::ApproveAgentArgs),
+    /// Print the asset id of every perp and spot pair, for --assets.
+    Assets,
+    /// Send signed `/exchange` bodies written with --output.
+    Broadcast(offline::BroadcastArgs),
+    /// Decode an `/exchange` payload and show what was hashed and who signed it.
+    Decode(decode::DecodeArgs),
+}
+
+/// Global options and the clients built from them.
+pub struct Context {
+    pub global: GlobalArgs,
+}
+
+impl Context {
+    /// `--base-url`, if given. Clients sign for mainnet only when they talk
+    /// to the mainnet API, so it is refused on mainnet.
+    fn base_url(&self) -> Result<Option<String>> {
+        match &self.global.base_url {
+            Some(_) if self.global.network == Network::Mainnet => Err(Error::GenericParse(
+                "--base-url cannot be used with --network mainnet".to_string(),
+            )),
+            base_url => Ok(base_url.clone()),
+        }
+    }
+
+    pub fn info_client(&self) -> Result<HyperliquidInfoClient> {
+        let mut client = HyperliquidInfoClient::new(Some(self.global.network.base_url()))?;
+        if let Some(base_url) = self.base_url()? {
+            client.set_base_url(base_url);
+        }
+        Ok(client)
+    }
+
+    /// Exchange client for `vault_address` on the API of `--network` or
+    /// `--base-url`.
+    pub fn new_exchange_client(
+        &self,
+        vault_address: Option<Address>,
+        coin_to_asset: HashMap<String, u32>,
+    ) -> Result<ExchangeClient> {
+        let mut client = ExchangeClient::new(
+            Some(self.global.network.base_url()),
+            vault_address,
+            coin_to_asset,
+        )?;
+        if let Some(base_url) = self.base_url()? {
+            client.set_base_url(base_url);
+        }
+        Ok(client)
+    }
+
+    /// Exchange client acting for `--vault`, with the assets of every perp
+    /// dex and spot pair loaded from `--assets` or the info endpoint.
+    pub async fn exchange_client(&self) -> Result<ExchangeClient> {
+        let coin_to_asset = match &self.global.assets {
+            Some(path) => offline::read_assets(path)?,
+            None => self.info_client()?.coin_to_asset().await?,
+        };
+        self.new_exchange_client(self.global.vault, coin_to_asset)
+    }
+
+    /// Exchange client acting for `--vault` with no assets, for actions that
+    /// name no coin. It never touches the network before sending.
+    pub fn exchange_client_without_assets(&self) -> Result<ExchangeClient> {
+        self.new_exchange_client(self.global.vault, HashMap::new())
+    }
+
+    pub fn signer(&self) -> Result<PrivateKeySigner> {
+        keys::load_signer(&self.global)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let Cli { global, command } = Cli::parse();
+    let context = Context { global };
+    let result = match command {
+        Command::Positions(args) => info::positions(&context, args).await,
+        Command::Orders(args) => info::orders(&context, args).await,
+        Command::Fills(args) => info::fills(&context, args).await,
+        Command::Book(args) => info::book(&context, args).await,
+        Command::Order(args) => exchange::order(&context, args).await,
+        Command::Cancel(args) => exchange::cancel(&context, args).await,
+        Command::Modify(args) => exchange::modify(&context, args).await,
+        Command::Leverage(args) => exchange::leverage(&context, args).await,
+        Command::Transfer(args) => exchange::transfer(&context, args).await,
+        Command::Withdraw(args) => exchange::withdraw(&context, args).await,
+        Command::ApproveAgent(args) => exchange::approve_agent(&context, args).await,
+        Command::Assets => offline::assets(&context).await,
+        Command::Broadcast(args) => offline::broadcast(&context, args).await,
+        Command::Decode(args) => decode::run(&context, args),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
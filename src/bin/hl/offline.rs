@@ -0,0 +1,112 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use hl_rs::{
+    Error, ExchangeClient, Result,
+    exchange::{SignedAction, decode_payload},
+};
+
+use crate::{Context, Network, exchange::report};
+
+#[derive(Args)]
+pub struct BroadcastArgs {
+    /// Files written by an exchange command run with --output.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+}
+
+/// Coin to asset map for `--assets`.
+pub fn read_assets(path: &Path) -> Result<HashMap<String, u32>> {
+    let assets = std::fs::read_to_string(path).map_err(|e| Error::GenericReader(e.to_string()))?;
+    serde_json::from_str(&assets).map_err(|e| Error::JsonParse(e.to_string()))
+}
+
+/// Print the coin to asset map of every perp dex and spot pair, for
+/// `--assets`.
+pub async fn assets(context: &Context) -> Result<()> {
+    let coin_to_asset: BTreeMap<_, _> = context
+        .info_client()?
+        .coin_to_asset()
+        .await?
+        .into_iter()
+        .collect();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&coin_to_asset)
+            .map_err(|e| Error::JsonParse(e.to_string()))?
+    );
+    Ok(())
+}
+
+/// Write the `/exchange` body of `signed` to a new file at `path`.
+pub fn write_signed(signed: &SignedAction, path: &Path) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| Error::GenericReader(format!("{}: {e}", path.display())))?;
+    writeln!(file, "{}", signed.to_payload()?)
+        .map_err(|e| Error::GenericReader(format!("{}: {e}", path.display())))?;
+
+    let action_type = signed.action["type"].as_str().unwrap_or("action");
+    eprintln!(
+        "wrote {action_type} with nonce {} to {}",
+        signed.nonce,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Send every file in order, reporting each response. Failed files do not
+/// stop the others.
+pub async fn broadcast(context: &Context, args: BroadcastArgs) -> Result<()> {
+    let client = context.new_exchange_client(None, HashMap::new())?;
+    let is_mainnet = context.global.network == Network::Mainnet;
+
+    let mut failed = 0;
+    for path in &args.files {
+        if let Err(err) = broadcast_file(&client, path, is_mainnet, context.global.json).await {
+            eprintln!("{}: error: {err}", path.display());
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::GenericRequest(format!(
+            "{failed} of {} payloads failed",
+            args.files.len()
+        )));
+    }
+    Ok(())
+}
+
+async fn broadcast_file(
+    client: &ExchangeClient,
+    path: &Path,
+    is_mainnet: bool,
+    json: bool,
+) -> Result<()> {
+    let payload = std::fs::read_to_string(path).map_err(|e| Error::GenericReader(e.to_string()))?;
+
+    let decoded = decode_payload(&payload, is_mainnet)?;
+    let Some(signer) = decoded.signer else {
+        return Err(Error::SignatureFailure(
+            "signature does not recover a signer".to_string(),
+        ));
+    };
+    if !json {
+        println!(
+            "{}: {} signed by {signer}",
+            path.display(),
+            decoded.action_json["type"].as_str().unwrap_or("action"),
+        );
+    }
+
+    let status = SignedAction::from_payload(&payload, client)?.send().await?;
+    report(status, json)
+}
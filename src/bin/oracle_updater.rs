@@ -0,0 +1,76 @@
+use std::{collections::HashMap, env, time::Duration};
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    BaseUrl, ExchangeClient,
+    info::HyperliquidInfoClient,
+    oracle::{ExchangeOracleSubmitter, MidsPriceSource, OracleUpdater, OracleUpdaterConfig},
+};
+use log::info;
+
+// Mirrors main dex mids onto a builder-deployed dex.
+//
+// HL_PRIVATE_KEY      deployer or oracle updater key
+// HL_NETWORK          mainnet, testnet or localhost (default testnet)
+// HL_DEX              dex to update
+// HL_ORACLE_COINS     comma separated `dex:COIN=REFERENCE`, e.g. `test:ABC=BTC`
+// HL_ORACLE_INTERVAL  seconds between updates (default 3)
+// HL_ORACLE_MAX_CHANGE largest accepted relative move (default 0.1)
+// HL_ORACLE_REANCHOR_AFTER rounds a larger move must hold to be accepted (default 3)
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let wallet: PrivateKeySigner = required("HL_PRIVATE_KEY")
+        .parse()
+        .expect("HL_PRIVATE_KEY is not a valid private key");
+    let base_url = match env::var("HL_NETWORK").as_deref() {
+        Ok("mainnet") => BaseUrl::Mainnet,
+        Ok("localhost") => BaseUrl::Localhost,
+        _ => BaseUrl::Testnet,
+    };
+    let dex = required("HL_DEX");
+    let coins = required("HL_ORACLE_COINS")
+        .split(',')
+        .map(|pair| {
+            let (coin, reference) = pair
+                .split_once('=')
+                .expect("HL_ORACLE_COINS entries must look like `dex:COIN=REFERENCE`");
+            (coin.trim().to_string(), reference.trim().to_string())
+        })
+        .collect();
+
+    let mut config = OracleUpdaterConfig::default();
+    if let Ok(interval) = env::var("HL_ORACLE_INTERVAL") {
+        config.interval = Duration::from_secs_f64(interval.parse().expect("invalid interval"));
+    }
+    if let Ok(max_change) = env::var("HL_ORACLE_MAX_CHANGE") {
+        config.max_change = max_change.parse().expect("invalid max change");
+    }
+    if let Ok(reanchor_after) = env::var("HL_ORACLE_REANCHOR_AFTER") {
+        config.reanchor_after = reanchor_after.parse().expect("invalid re-anchor rounds");
+    }
+
+    let source = MidsPriceSource {
+        info_client: HyperliquidInfoClient::new(Some(base_url)).unwrap(),
+        dex,
+        reference_dex: None,
+        coins,
+    };
+    let submitter = ExchangeOracleSubmitter {
+        client: ExchangeClient::new(Some(base_url), None, HashMap::new()).unwrap(),
+        wallet,
+    };
+
+    info!("starting oracle updater");
+    OracleUpdater::new(source, submitter, config)
+        .run_until(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await;
+    info!("oracle updater stopped");
+}
+
+fn required(name: &str) -> String {
+    env::var(name).unwrap_or_else(|_| panic!("{name} is not set"))
+}
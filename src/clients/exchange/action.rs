@@ -1,23 +1,65 @@
 use alloy::{
-    primitives::{Address, B256, Signature},
+    primitives::{Address, B256, Signature, U256},
     signers::{SignerSync, local::PrivateKeySigner},
 };
 use serde::{Deserialize, Serialize, Serializer, ser::SerializeStruct};
 use serde_json::Value;
 
-use crate::{Error, Result, http::HttpClient, utils::sign_l1_action};
+use crate::{
+    Error, ExchangeClient, Result,
+    http::HttpClient,
+    utils::{l1_action_signing_hash, sign_l1_action},
+};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ExchangePayload {
-    action: serde_json::Value,
+struct ExchangePayload<'a> {
+    action: &'a Value,
     #[serde(serialize_with = "serialize_sig")]
-    signature: Signature,
+    signature: &'a Signature,
+    nonce: u64,
+    vault_address: Option<Address>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedPayload {
+    action: Value,
+    signature: WireSignature,
     nonce: u64,
     vault_address: Option<Address>,
 }
 
-fn serialize_sig<S>(sig: &Signature, s: S) -> std::result::Result<S::Ok, S::Error>
+/// Signature as sent to `/exchange`, with `v` of 27 or 28.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct WireSignature {
+    r: U256,
+    s: U256,
+    v: u64,
+}
+
+impl From<Signature> for WireSignature {
+    fn from(signature: Signature) -> Self {
+        Self {
+            r: signature.r(),
+            s: signature.s(),
+            v: 27 + signature.v() as u64,
+        }
+    }
+}
+
+impl WireSignature {
+    pub(crate) fn into_signature(self) -> Result<Signature> {
+        let parity = match self.v {
+            0 | 27 => false,
+            1 | 28 => true,
+            v => return Err(Error::SignatureFailure(format!("invalid v {v}"))),
+        };
+        Ok(Signature::new(self.r, self.s, parity))
+    }
+}
+
+fn serialize_sig<S>(sig: &&Signature, s: S) -> std::result::Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -28,6 +70,19 @@ where
     state.end()
 }
 
+/// Signer of built actions, for keys that live outside the process, such as
+/// [`RemoteSigner`](crate::signer::RemoteSigner).
+pub trait ActionSigner {
+    fn sign_action(&self, action: &Action) -> impl Future<Output = Result<Signature>> + Send;
+}
+
+impl ActionSigner for PrivateKeySigner {
+    async fn sign_action(&self, action: &Action) -> Result<Signature> {
+        self.sign_hash_sync(&action.signing_data.signing_hash())
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+}
+
 /// Unsigned action ready to be signed.
 ///
 /// Represents a fully prepared action that has been built with all
@@ -36,8 +91,23 @@ where
 ///
 /// # Example
 /// ```no_run
+/// # use std::collections::HashMap;
+/// # use alloy::signers::local::PrivateKeySigner;
+/// # use hl_rs::exchange::{ActionKind, ExchangeClient, builder::BuildAction, requests::UsdSend};
+/// # fn main() -> hl_rs::Result<()> {
+/// # let client = ExchangeClient::new(None, None, HashMap::new())?;
+/// # let wallet = PrivateKeySigner::random();
+/// # let usd_send = UsdSend {
+/// #     signature_chain_id: 421614,
+/// #     hyperliquid_chain: "Testnet".to_string(),
+/// #     destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+/// #     amount: "1".to_string(),
+/// #     time: 1690393044548,
+/// # };
 /// let action = ActionKind::UsdSend(usd_send).build(&client)?;
 /// let signed = action.sign(&wallet)?;
+/// # Ok(())
+/// # }
 /// ```
 pub struct Action {
     pub action: Value,
@@ -59,6 +129,19 @@ pub enum SigningData {
     TypedData { hash: B256 },
 }
 
+impl SigningData {
+    /// Hash the signature is made over.
+    pub fn signing_hash(&self) -> B256 {
+        match *self {
+            SigningData::L1 {
+                connection_id,
+                is_mainnet,
+            } => l1_action_signing_hash(connection_id, is_mainnet),
+            SigningData::TypedData { hash } => hash,
+        }
+    }
+}
+
 /// Signed action ready to be sent to the Hyperliquid API.
 ///
 /// This action has been fully prepared and signed, and can be sent
@@ -93,6 +176,12 @@ impl Action {
         })
     }
 
+    /// Sign action with any [`ActionSigner`].
+    pub async fn sign_with<S: ActionSigner>(self, signer: &S) -> Result<SignedAction> {
+        let signature = signer.sign_action(&self).await?;
+        Ok(self.with_signature(signature))
+    }
+
     /// Attach externally-provided signature to this action.
     /// Use this when signing is done outside the SDK (e.g., using Nitro Enclave).
     pub fn with_signature(self, signature: Signature) -> SignedAction {
@@ -112,20 +201,75 @@ impl Action {
 }
 
 impl SignedAction {
-    /// Send signed action to Hyperliquid API.
-    pub async fn send(self) -> Result<crate::exchange::responses::ExchangeResponseStatus> {
-        let exchange_payload = ExchangePayload {
-            action: self.action,
-            signature: self.signature,
+    /// JSON body of the `/exchange` request sending this action.
+    pub fn to_payload(&self) -> Result<String> {
+        serde_json::to_string(&ExchangePayload {
+            action: &self.action,
+            signature: &self.signature,
             nonce: self.nonce,
             vault_address: self.vault_address,
-        };
+        })
+        .map_err(|e| Error::JsonParse(e.to_string()))
+    }
 
-        let res = serde_json::to_string(&exchange_payload)
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+    /// Signed action from an `/exchange` body written by
+    /// [`SignedAction::to_payload`], to be sent through `client`. The
+    /// vault address is taken from the payload, not from `client`.
+    pub fn from_payload(payload: &str, client: &ExchangeClient) -> Result<Self> {
+        let payload: SignedPayload =
+            serde_json::from_str(payload).map_err(|e| Error::JsonParse(e.to_string()))?;
 
-        let output = self.http_client.post("/exchange", res).await?;
+        Ok(Self {
+            action: payload.action,
+            nonce: payload.nonce,
+            signature: payload.signature.into_signature()?,
+            vault_address: payload.vault_address,
+            http_client: client.http_client().clone(),
+        })
+    }
+
+    /// Send signed action to Hyperliquid API.
+    pub async fn send(self) -> Result<crate::exchange::responses::ExchangeResponseStatus> {
+        let output = self
+            .http_client
+            .post("/exchange", self.to_payload()?)
+            .await?;
 
         serde_json::from_str(&output).map_err(|e| Error::JsonParse(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        BaseUrl,
+        exchange::{ActionKind, builder::BuildAction, requests::UpdateLeverage},
+    };
+
+    #[test]
+    fn test_payload_round_trip() -> Result<()> {
+        let wallet = PrivateKeySigner::random();
+        let vault = Address::repeat_byte(0x22);
+        let client = ExchangeClient::new(Some(BaseUrl::Testnet), Some(vault), HashMap::new())?;
+        let signed = ActionKind::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        })
+        .build(&client)?
+        .sign(&wallet)?;
+
+        let payload = signed.to_payload()?;
+        let offline = ExchangeClient::new(Some(BaseUrl::Mainnet), None, HashMap::new())?;
+        let parsed = SignedAction::from_payload(&payload, &offline)?;
+        assert_eq!(parsed.signature, signed.signature);
+        assert_eq!(parsed.nonce, signed.nonce);
+        assert_eq!(parsed.vault_address, Some(vault));
+        assert_eq!(parsed.to_payload()?, payload);
+        assert!(parsed.http_client.is_mainnet());
+        Ok(())
+    }
+}
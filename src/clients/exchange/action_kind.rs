@@ -5,8 +5,11 @@ use crate::{
     Error, Result,
     exchange::requests::{
         ApproveAgent, ApproveBuilderFee, BulkCancel, BulkCancelCloid, BulkModify, BulkOrder,
-        ClaimRewards, EvmUserModify, PerpDeploy, ScheduleCancel, SendAsset, SetReferrer, SpotSend,
-        SpotUser, UpdateIsolatedMargin, UpdateLeverage, UsdSend, VaultTransfer, Withdraw3,
+        CDeposit, CWithdraw, ClaimRewards, ConvertToMultiSigUser, CreateSubAccount, CreateVault,
+        EvmUserModify, MultiSig, Noop, PerpDeploy, ScheduleCancel, SendAsset, SetReferrer,
+        SpotDeploy, SpotSend, SpotUser, SubAccountSpotTransfer, SubAccountTransfer, TokenDelegate,
+        TwapCancel, TwapOrder, UpdateIsolatedMargin, UpdateLeverage, UsdSend, VaultDistribute,
+        VaultModify, VaultTransfer, Withdraw3,
     },
 };
 
@@ -33,19 +36,43 @@ pub enum ActionKind {
     ScheduleCancel(ScheduleCancel),
     ClaimRewards(ClaimRewards),
     PerpDeploy(PerpDeploy),
+    TwapOrder(TwapOrder),
+    TwapCancel(TwapCancel),
+    CreateSubAccount(CreateSubAccount),
+    SubAccountTransfer(SubAccountTransfer),
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
+    CreateVault(CreateVault),
+    VaultModify(VaultModify),
+    VaultDistribute(VaultDistribute),
+    CDeposit(CDeposit),
+    CWithdraw(CWithdraw),
+    TokenDelegate(TokenDelegate),
+    SpotDeploy(SpotDeploy),
+    ConvertToMultiSigUser(ConvertToMultiSigUser),
+    MultiSig(MultiSig),
+    Noop(Noop),
 }
 
 impl ActionKind {
     pub fn hash(&self, timestamp: u64, vault_address: Option<Address>) -> Result<B256> {
-        let mut bytes =
-            rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))?;
-        bytes.extend(timestamp.to_be_bytes());
-        if let Some(vault_address) = vault_address {
-            bytes.push(1);
-            bytes.extend(vault_address);
-        } else {
-            bytes.push(0);
-        }
-        Ok(keccak256(bytes))
+        l1_action_hash(self, timestamp, vault_address)
     }
 }
+
+/// Connection id of an L1 action: keccak256 of the msgpack-encoded action
+/// followed by the nonce and the optional vault address.
+pub(crate) fn l1_action_hash<T: Serialize + ?Sized>(
+    action: &T,
+    timestamp: u64,
+    vault_address: Option<Address>,
+) -> Result<B256> {
+    let mut bytes = rmp_serde::to_vec_named(action).map_err(|e| Error::RmpParse(e.to_string()))?;
+    bytes.extend(timestamp.to_be_bytes());
+    if let Some(vault_address) = vault_address {
+        bytes.push(1);
+        bytes.extend(vault_address);
+    } else {
+        bytes.push(0);
+    }
+    Ok(keccak256(bytes))
+}
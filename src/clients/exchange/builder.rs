@@ -1,9 +1,20 @@
-use alloy::primitives::{Address, B256};
+use alloy::{
+    primitives::{Address, B256},
+    signers::local::PrivateKeySigner,
+};
+use serde::Serialize;
 
 use crate::{
     Error, Result,
     eip712::Eip712,
-    exchange::{Action, ActionKind, ExchangeClient, SigningData},
+    exchange::{
+        Action, ActionKind, ExchangeClient, SignedAction, SigningData,
+        requests::{
+            APPROVE_AGENT_TYPE, APPROVE_BUILDER_FEE_TYPE, C_DEPOSIT_TYPE, C_WITHDRAW_TYPE,
+            CONVERT_TO_MULTI_SIG_USER_TYPE, Noop, SEND_ASSET_TYPE, SPOT_SEND_TYPE,
+            TOKEN_DELEGATE_TYPE, USD_SEND_TYPE, WITHDRAW_TYPE,
+        },
+    },
     utils::next_nonce,
 };
 
@@ -12,22 +23,38 @@ pub trait BuildAction {
 }
 
 impl BuildAction for ActionKind {
-    fn build(self, client: &ExchangeClient) -> Result<Action> {
-        let timestamp = next_nonce();
+    fn build(mut self, client: &ExchangeClient) -> Result<Action> {
+        if let ActionKind::MultiSig(_) = self {
+            return Err(Error::MultiSig(
+                "multiSig actions are built with MultiSigProposal::aggregate".to_string(),
+            ));
+        }
+
+        if let ActionKind::Order(bulk_order) = &mut self
+            && bulk_order.builder.is_none()
+        {
+            bulk_order.builder = client.builder().cloned();
+        }
+
+        if let Some(risk_checker) = client.risk_checker() {
+            risk_checker.lock().unwrap().check(&self)?;
+        }
+
         let vault_address = client.vault_address();
 
         let is_l1_action = self.is_l1_action();
 
         if is_l1_action {
-            self.build_l1_action(client, timestamp, vault_address)
+            self.build_l1_action(client, next_nonce(), vault_address)
         } else {
+            let timestamp = self.typed_data_nonce()?;
             self.build_typed_data_action(client, timestamp, vault_address)
         }
     }
 }
 
 impl ActionKind {
-    fn is_l1_action(&self) -> bool {
+    pub(crate) fn is_l1_action(&self) -> bool {
         matches!(
             self,
             ActionKind::Order(_)
@@ -43,6 +70,16 @@ impl ActionKind {
                 | ActionKind::ScheduleCancel(_)
                 | ActionKind::ClaimRewards(_)
                 | ActionKind::PerpDeploy(_)
+                | ActionKind::TwapOrder(_)
+                | ActionKind::TwapCancel(_)
+                | ActionKind::CreateSubAccount(_)
+                | ActionKind::SubAccountTransfer(_)
+                | ActionKind::SubAccountSpotTransfer(_)
+                | ActionKind::CreateVault(_)
+                | ActionKind::VaultModify(_)
+                | ActionKind::VaultDistribute(_)
+                | ActionKind::SpotDeploy(_)
+                | ActionKind::Noop(_)
         )
     }
 
@@ -53,19 +90,7 @@ impl ActionKind {
         vault_address: Option<Address>,
     ) -> Result<Action> {
         let connection_id = self.hash(timestamp, vault_address)?;
-        let action_json =
-            serde_json::to_value(&self).map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        Ok(Action {
-            action: action_json,
-            nonce: timestamp,
-            vault_address,
-            signing_data: SigningData::L1 {
-                connection_id,
-                is_mainnet: client.is_mainnet(),
-            },
-            http_client: client.http_client().clone(),
-        })
+        l1_action(&self, connection_id, client, timestamp, vault_address)
     }
 
     fn build_typed_data_action(
@@ -75,19 +100,10 @@ impl ActionKind {
         vault_address: Option<Address>,
     ) -> Result<Action> {
         let hash = self.extract_eip712_hash()?;
-        let action_json =
-            serde_json::to_value(&self).map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        Ok(Action {
-            action: action_json,
-            nonce: timestamp,
-            vault_address,
-            signing_data: SigningData::TypedData { hash },
-            http_client: client.http_client().clone(),
-        })
+        typed_data_action(&self, hash, client, timestamp, vault_address)
     }
 
-    fn extract_eip712_hash(&self) -> Result<B256> {
+    pub(crate) fn extract_eip712_hash(&self) -> Result<B256> {
         match self {
             ActionKind::UsdSend(usd_send) => Ok(usd_send.eip712_signing_hash()),
             ActionKind::Withdraw3(withdraw) => Ok(withdraw.eip712_signing_hash()),
@@ -97,9 +113,161 @@ impl ActionKind {
             ActionKind::ApproveBuilderFee(approve_builder_fee) => {
                 Ok(approve_builder_fee.eip712_signing_hash())
             }
+            ActionKind::CDeposit(c_deposit) => Ok(c_deposit.eip712_signing_hash()),
+            ActionKind::CWithdraw(c_withdraw) => Ok(c_withdraw.eip712_signing_hash()),
+            ActionKind::TokenDelegate(token_delegate) => Ok(token_delegate.eip712_signing_hash()),
+            ActionKind::ConvertToMultiSigUser(convert) => Ok(convert.eip712_signing_hash()),
+            _ => Err(Error::GenericParse(
+                "Action type not supported for typed data signing".to_string(),
+            )),
+        }
+    }
+
+    /// User-signed actions carry their own nonce (`time` or `nonce`), which
+    /// the exchange expects to match the nonce of the request.
+    pub(crate) fn typed_data_nonce(&self) -> Result<u64> {
+        match self {
+            ActionKind::UsdSend(usd_send) => Ok(usd_send.time),
+            ActionKind::Withdraw3(withdraw) => Ok(withdraw.time),
+            ActionKind::SpotSend(spot_send) => Ok(spot_send.time),
+            ActionKind::SendAsset(send_asset) => Ok(send_asset.nonce),
+            ActionKind::ApproveAgent(approve_agent) => Ok(approve_agent.nonce),
+            ActionKind::ApproveBuilderFee(approve_builder_fee) => Ok(approve_builder_fee.nonce),
+            ActionKind::CDeposit(c_deposit) => Ok(c_deposit.nonce),
+            ActionKind::CWithdraw(c_withdraw) => Ok(c_withdraw.nonce),
+            ActionKind::TokenDelegate(token_delegate) => Ok(token_delegate.nonce),
+            ActionKind::ConvertToMultiSigUser(convert) => Ok(convert.nonce),
             _ => Err(Error::GenericParse(
                 "Action type not supported for typed data signing".to_string(),
             )),
         }
     }
+
+    /// EIP-712 type string of a user-signed action.
+    pub(crate) fn user_signed_type(&self) -> Option<&'static str> {
+        match self {
+            ActionKind::UsdSend(_) => Some(USD_SEND_TYPE),
+            ActionKind::Withdraw3(_) => Some(WITHDRAW_TYPE),
+            ActionKind::SpotSend(_) => Some(SPOT_SEND_TYPE),
+            ActionKind::SendAsset(_) => Some(SEND_ASSET_TYPE),
+            ActionKind::ApproveAgent(_) => Some(APPROVE_AGENT_TYPE),
+            ActionKind::ApproveBuilderFee(_) => Some(APPROVE_BUILDER_FEE_TYPE),
+            ActionKind::CDeposit(_) => Some(C_DEPOSIT_TYPE),
+            ActionKind::CWithdraw(_) => Some(C_WITHDRAW_TYPE),
+            ActionKind::TokenDelegate(_) => Some(TOKEN_DELEGATE_TYPE),
+            ActionKind::ConvertToMultiSigUser(_) => Some(CONVERT_TO_MULTI_SIG_USER_TYPE),
+            _ => None,
+        }
+    }
+}
+
+/// Sign a `noop` action with `nonce`.
+///
+/// Sending it consumes the nonce, so a pre-signed action that used the same
+/// nonce can no longer be broadcast.
+pub fn sign_noop(
+    client: &ExchangeClient,
+    wallet: &PrivateKeySigner,
+    nonce: u64,
+) -> Result<SignedAction> {
+    ActionKind::Noop(Noop)
+        .build_l1_action(client, nonce, client.vault_address())?
+        .sign(wallet)
+}
+
+pub(crate) fn l1_action<T: Serialize + ?Sized>(
+    action: &T,
+    connection_id: B256,
+    client: &ExchangeClient,
+    timestamp: u64,
+    vault_address: Option<Address>,
+) -> Result<Action> {
+    let action_json = serde_json::to_value(action).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+    Ok(Action {
+        action: action_json,
+        nonce: timestamp,
+        vault_address,
+        signing_data: SigningData::L1 {
+            connection_id,
+            is_mainnet: client.is_mainnet(),
+        },
+        http_client: client.http_client().clone(),
+    })
+}
+
+pub(crate) fn typed_data_action<T: Serialize + ?Sized>(
+    action: &T,
+    hash: B256,
+    client: &ExchangeClient,
+    timestamp: u64,
+    vault_address: Option<Address>,
+) -> Result<Action> {
+    let action_json = serde_json::to_value(action).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+    Ok(Action {
+        action: action_json,
+        nonce: timestamp,
+        vault_address,
+        signing_data: SigningData::TypedData { hash },
+        http_client: client.http_client().clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::exchange::requests::{BuilderInfo, BulkOrder};
+
+    #[test]
+    fn test_sign_noop() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let wallet = PrivateKeySigner::random();
+
+        let signed = sign_noop(&client, &wallet, 1_700_000_000_000)?;
+        assert_eq!(signed.nonce, 1_700_000_000_000);
+        assert_eq!(signed.action, serde_json::json!({ "type": "noop" }));
+
+        let connection_id = ActionKind::Noop(Noop).hash(signed.nonce, None)?;
+        let hash = SigningData::L1 {
+            connection_id,
+            is_mainnet: true,
+        }
+        .signing_hash();
+        assert_eq!(
+            signed.signature.recover_address_from_prehash(&hash).ok(),
+            Some(wallet.address())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_attaches_default_builder() -> Result<()> {
+        let mut client = ExchangeClient::new(None, None, HashMap::new())?;
+        client.set_builder(Some(BuilderInfo::new(Address::repeat_byte(0xAB), 10)));
+        let order = |builder: Option<BuilderInfo>| {
+            ActionKind::Order(BulkOrder {
+                orders: vec![],
+                grouping: "na".to_string(),
+                builder,
+            })
+        };
+
+        let action = order(None).build(&client)?;
+        assert_eq!(
+            action.action["builder"],
+            serde_json::json!({ "b": format!("0x{}", "ab".repeat(20)), "f": 10 })
+        );
+
+        let own = BuilderInfo::new(Address::repeat_byte(0x01), 1);
+        let action = order(Some(own)).build(&client)?;
+        assert_eq!(action.action["builder"]["f"], 1);
+
+        let approve = client.approve_builder_fee()?.unwrap();
+        assert_eq!(approve.builder, Address::repeat_byte(0xAB));
+        assert_eq!(approve.max_fee_rate, "0.01%");
+        Ok(())
+    }
 }
@@ -1,14 +1,29 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use alloy::primitives::Address;
 
-use crate::{BaseUrl, http::HttpClient, prelude::Result};
+use crate::{
+    BaseUrl, Error,
+    consts::SIGNATURE_CHAIN_ID,
+    dry_run::DryRun,
+    exchange::requests::{ApproveBuilderFee, BuilderInfo, resolve_asset},
+    http::{Cassette, HttpClient},
+    info::HyperliquidInfoClient,
+    prelude::Result,
+    risk::RiskChecker,
+    utils::{hyperliquid_chain, next_nonce},
+};
 
 #[derive(Debug, Clone)]
 pub struct ExchangeClient {
     http_client: HttpClient,
     vault_address: Option<Address>,
     coin_to_asset: HashMap<String, u32>,
+    builder: Option<BuilderInfo>,
+    risk_checker: Option<Arc<Mutex<RiskChecker>>>,
 }
 
 impl ExchangeClient {
@@ -23,9 +38,13 @@ impl ExchangeClient {
             http_client: HttpClient {
                 client: reqwest::Client::default(),
                 base_url: base_url.get_url(),
+                cassette: None,
+                dry_run: None,
             },
             vault_address,
             coin_to_asset,
+            builder: None,
+            risk_checker: None,
         })
     }
 
@@ -37,11 +56,110 @@ impl ExchangeClient {
         self.http_client.is_mainnet()
     }
 
+    /// `hyperliquidChain` of user-signed actions sent through this client.
+    pub fn hyperliquid_chain(&self) -> &'static str {
+        hyperliquid_chain(self.is_mainnet())
+    }
+
     pub(crate) fn http_client(&self) -> &HttpClient {
         &self.http_client
     }
 
+    /// Record requests to, or replay them from, `cassette`. Actions built
+    /// afterwards send through it.
+    pub fn set_cassette(&mut self, cassette: Option<Arc<Cassette>>) {
+        self.http_client.cassette = cassette;
+    }
+
+    /// Send requests to `url`, such as a local mock, instead of the API of
+    /// the network the client was created for. Actions are signed for
+    /// mainnet only when `url` is the mainnet API.
+    pub fn set_base_url(&mut self, url: String) {
+        self.http_client.base_url = url;
+    }
+
+    /// Log actions in `dry_run` instead of sending them. Actions built
+    /// afterwards get synthetic responses.
+    pub fn set_dry_run(&mut self, dry_run: Option<Arc<DryRun>>) {
+        self.http_client.dry_run = dry_run;
+    }
+
+    pub fn dry_run(&self) -> Option<&Arc<DryRun>> {
+        self.http_client.dry_run.as_ref()
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn http_client_mut(&mut self) -> &mut HttpClient {
+        &mut self.http_client
+    }
+
     pub fn coin_to_asset(&self) -> &HashMap<String, u32> {
         &self.coin_to_asset
     }
+
+    /// Asset id of `coin`, including "dex:COIN" assets of builder-deployed
+    /// perp dexs and spot pairs loaded with
+    /// `HyperliquidInfoClient::coin_to_asset`.
+    pub fn asset(&self, coin: &str) -> Result<u32> {
+        resolve_asset(&self.coin_to_asset, coin)
+    }
+
+    /// Builder attached to every order built through this client that does
+    /// not set one itself.
+    pub fn set_builder(&mut self, builder: Option<BuilderInfo>) {
+        self.builder = builder;
+    }
+
+    pub fn builder(&self) -> Option<&BuilderInfo> {
+        self.builder.as_ref()
+    }
+
+    /// Risk checker every action built through this client must pass. The
+    /// checker is shared, so clones of the client count towards the same
+    /// rate limit.
+    pub fn set_risk_checker(&mut self, risk_checker: Option<Arc<Mutex<RiskChecker>>>) {
+        self.risk_checker = risk_checker;
+    }
+
+    pub fn risk_checker(&self) -> Option<&Arc<Mutex<RiskChecker>>> {
+        self.risk_checker.as_ref()
+    }
+
+    /// `ApproveBuilderFee` allowing the default builder to charge its fee,
+    /// or `None` if no builder is set.
+    pub fn approve_builder_fee(&self) -> Result<Option<ApproveBuilderFee>> {
+        let Some(builder) = &self.builder else {
+            return Ok(None);
+        };
+
+        Ok(Some(ApproveBuilderFee {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            hyperliquid_chain: self.hyperliquid_chain().to_string(),
+            builder: builder
+                .builder
+                .parse()
+                .map_err(|e: alloy::hex::FromHexError| Error::GenericParse(e.to_string()))?,
+            max_fee_rate: builder.max_fee_rate(),
+            nonce: next_nonce(),
+        }))
+    }
+
+    /// Check through the `maxBuilderFee` query that `user` approved the
+    /// default builder's fee, returning the `ApproveBuilderFee` to sign
+    /// with the user's key when the approval is missing or too low.
+    pub async fn builder_fee_approval(
+        &self,
+        info_client: &HyperliquidInfoClient,
+        user: Address,
+    ) -> Result<Option<ApproveBuilderFee>> {
+        let Some(approve_builder_fee) = self.approve_builder_fee()? else {
+            return Ok(None);
+        };
+        let fee = self.builder.as_ref().map_or(0, |builder| builder.fee);
+
+        let approved = info_client
+            .max_builder_fee(user, approve_builder_fee.builder)
+            .await?;
+        Ok((approved < fee).then_some(approve_builder_fee))
+    }
 }
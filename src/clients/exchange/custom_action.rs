@@ -0,0 +1,250 @@
+use alloy::primitives::B256;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    Error, Result,
+    eip712::Eip712,
+    exchange::{
+        Action, ExchangeClient,
+        action_kind::l1_action_hash,
+        builder::{BuildAction, l1_action, typed_data_action},
+    },
+    utils::next_nonce,
+};
+
+/// Action hashed with msgpack and signed through the L1 `Agent` wrapper.
+///
+/// Implement this on your own struct to send exchange actions that are not
+/// covered by [`ActionKind`](crate::exchange::ActionKind). The struct must
+/// serialize its fields exactly as the exchange expects them, since field
+/// order and names are part of the hash.
+///
+/// # Example
+/// ```no_run
+/// # use std::collections::HashMap;
+/// # use alloy::signers::local::PrivateKeySigner;
+/// # use hl_rs::exchange::{CustomL1Action, ExchangeClient, L1Action, builder::BuildAction};
+/// # use serde::Serialize;
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct TwapCancel {
+///     a: u32,
+///     t: u64,
+/// }
+///
+/// impl L1Action for TwapCancel {
+///     const ACTION_TYPE: &'static str = "twapCancel";
+/// }
+///
+/// # fn main() -> hl_rs::Result<()> {
+/// # let client = ExchangeClient::new(None, None, HashMap::new())?;
+/// # let wallet = PrivateKeySigner::random();
+/// let signed = CustomL1Action(TwapCancel { a: 0, t: 1 })
+///     .build(&client)?
+///     .sign(&wallet)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait L1Action: Serialize {
+    /// Value of the `type` field, e.g. `"twapOrder"`.
+    const ACTION_TYPE: &'static str;
+}
+
+/// Action signed directly by the user as EIP-712 typed data.
+pub trait UserSignedAction: Serialize + Eip712 {
+    /// Value of the `type` field, e.g. `"usdSend"`.
+    const ACTION_TYPE: &'static str;
+
+    /// Nonce of the action (its `time` or `nonce` field), which must match
+    /// the nonce of the request.
+    fn nonce(&self) -> u64;
+}
+
+/// Wrapper that builds an [`L1Action`] through [`BuildAction`].
+#[derive(Debug, Clone)]
+pub struct CustomL1Action<T>(pub T);
+
+/// Wrapper that builds a [`UserSignedAction`] through [`BuildAction`].
+#[derive(Debug, Clone)]
+pub struct CustomUserSignedAction<T>(pub T);
+
+/// Untyped action, for when defining a struct is not worth it.
+///
+/// The JSON must include the `type` field. L1 actions are hashed in the
+/// field order of the object, so keep it identical to the official SDKs.
+#[derive(Debug, Clone)]
+pub enum RawAction {
+    L1(Value),
+    UserSigned {
+        action: Value,
+        hash: B256,
+        nonce: u64,
+    },
+}
+
+/// Serializes an action with its `type` tag first, the same layout as an
+/// internally tagged [`ActionKind`](crate::exchange::ActionKind) variant.
+#[derive(Serialize)]
+struct Tagged<'a, T> {
+    #[serde(rename = "type")]
+    action_type: &'static str,
+    #[serde(flatten)]
+    action: &'a T,
+}
+
+/// Run the client's risk checker, if any, on an untyped action.
+fn check_risk<T: Serialize>(client: &ExchangeClient, action: &T) -> Result<()> {
+    let Some(risk_checker) = client.risk_checker() else {
+        return Ok(());
+    };
+    let action = serde_json::to_value(action).map_err(|e| Error::JsonParse(e.to_string()))?;
+    risk_checker.lock().unwrap().check_raw(&action)
+}
+
+impl<T: L1Action> BuildAction for CustomL1Action<T> {
+    fn build(self, client: &ExchangeClient) -> Result<Action> {
+        let timestamp = next_nonce();
+        let vault_address = client.vault_address();
+        let tagged = Tagged {
+            action_type: T::ACTION_TYPE,
+            action: &self.0,
+        };
+        check_risk(client, &tagged)?;
+
+        let connection_id = l1_action_hash(&tagged, timestamp, vault_address)?;
+        l1_action(&tagged, connection_id, client, timestamp, vault_address)
+    }
+}
+
+impl<T: UserSignedAction> BuildAction for CustomUserSignedAction<T> {
+    fn build(self, client: &ExchangeClient) -> Result<Action> {
+        let tagged = Tagged {
+            action_type: T::ACTION_TYPE,
+            action: &self.0,
+        };
+        check_risk(client, &tagged)?;
+
+        typed_data_action(
+            &tagged,
+            self.0.eip712_signing_hash(),
+            client,
+            self.0.nonce(),
+            client.vault_address(),
+        )
+    }
+}
+
+impl BuildAction for RawAction {
+    fn build(self, client: &ExchangeClient) -> Result<Action> {
+        let vault_address = client.vault_address();
+
+        match self {
+            RawAction::L1(action) => {
+                check_type_field(&action)?;
+                check_risk(client, &action)?;
+                let timestamp = next_nonce();
+                let connection_id = l1_action_hash(&action, timestamp, vault_address)?;
+                l1_action(&action, connection_id, client, timestamp, vault_address)
+            }
+            RawAction::UserSigned {
+                action,
+                hash,
+                nonce,
+            } => {
+                check_type_field(&action)?;
+                check_risk(client, &action)?;
+                typed_data_action(&action, hash, client, nonce, vault_address)
+            }
+        }
+    }
+}
+
+fn check_type_field(action: &Value) -> Result<()> {
+    match action.get("type") {
+        Some(Value::String(_)) => Ok(()),
+        _ => Err(Error::GenericParse(
+            "Raw action must have a string `type` field".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::exchange::{ActionKind, SigningData, requests::UpdateLeverage};
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CustomUpdateLeverage {
+        asset: u32,
+        is_cross: bool,
+        leverage: u32,
+    }
+
+    impl L1Action for CustomUpdateLeverage {
+        const ACTION_TYPE: &'static str = "updateLeverage";
+    }
+
+    fn connection_id(action: &Action) -> B256 {
+        match action.signing_data() {
+            SigningData::L1 { connection_id, .. } => *connection_id,
+            SigningData::TypedData { .. } => panic!("expected an L1 action"),
+        }
+    }
+
+    #[test]
+    fn test_custom_l1_action_hashes_like_action_kind() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let action = CustomL1Action(CustomUpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 10,
+        })
+        .build(&client)?;
+
+        let expected = ActionKind::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 10,
+        })
+        .hash(action.nonce, None)?;
+
+        assert_eq!(connection_id(&action), expected);
+        assert_eq!(action.action["type"], "updateLeverage");
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_l1_action_hashes_like_action_kind() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let action = RawAction::L1(serde_json::json!({
+            "type": "updateLeverage",
+            "asset": 1,
+            "isCross": true,
+            "leverage": 10,
+        }))
+        .build(&client)?;
+
+        let expected = ActionKind::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 10,
+        })
+        .hash(action.nonce, None)?;
+
+        assert_eq!(connection_id(&action), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_action_requires_type() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let result = RawAction::L1(serde_json::json!({ "asset": 1 })).build(&client);
+
+        assert!(result.is_err());
+        Ok(())
+    }
+}
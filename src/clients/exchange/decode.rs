@@ -0,0 +1,263 @@
+use std::fmt;
+
+use alloy::primitives::{Address, B256, Signature, hex};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    Eip712, Error, Result,
+    exchange::{ActionKind, WireSignature, l1_action_hash, requests::SendMultiSig},
+    utils::{hyperliquid_chain, l1_action_signing_hash},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangePayload {
+    action: Value,
+    nonce: u64,
+    signature: Option<WireSignature>,
+    vault_address: Option<Address>,
+}
+
+/// An `/exchange` payload taken apart, as produced by [`decode_payload`].
+#[derive(Debug, Clone)]
+pub struct DecodedPayload {
+    pub action: ActionKind,
+    /// Action as it appeared in the payload.
+    pub action_json: Value,
+    pub nonce: u64,
+    pub vault_address: Option<Address>,
+    pub signature: Option<Signature>,
+    pub is_l1_action: bool,
+    /// msgpack encoding of the re-parsed action, for L1 and `multiSig`
+    /// actions.
+    pub msgpack: Option<Vec<u8>>,
+    /// Connection id of an L1 action, or `multiSigActionHash` of a
+    /// `multiSig` action.
+    pub connection_id: Option<B256>,
+    /// Hash the signature is made over.
+    pub signing_hash: B256,
+    /// `None` when the payload is unsigned or the signer cannot be
+    /// recovered.
+    pub signer: Option<Address>,
+    /// Whether the re-parsed action serializes back to exactly the
+    /// payload's action. When it does not, the sender hashed an encoding
+    /// this SDK would not produce, typically with fields in another order.
+    /// Always true for user-signed actions, whose signature does not depend
+    /// on the JSON encoding.
+    pub round_trips: bool,
+}
+
+/// Decode an `/exchange` JSON payload, recompute what was hashed and
+/// recover the signer. `is_mainnet` selects the source of L1 actions and
+/// the `hyperliquidChain` of `multiSig` actions, which the payload does not
+/// carry.
+pub fn decode_payload(payload: &str, is_mainnet: bool) -> Result<DecodedPayload> {
+    let payload: ExchangePayload =
+        serde_json::from_str(payload).map_err(|e| Error::JsonParse(e.to_string()))?;
+    let action: ActionKind = serde_json::from_value(payload.action.clone())
+        .map_err(|e| Error::JsonParse(format!("action: {e}")))?;
+    let is_l1_action = action.is_l1_action();
+    let round_trips = !(is_l1_action || matches!(action, ActionKind::MultiSig(_)))
+        || serde_json::to_string(&action).ok() == Some(payload.action.to_string());
+
+    let (msgpack, connection_id, signing_hash) = match &action {
+        ActionKind::MultiSig(multi_sig) => {
+            let multi_sig_action_hash =
+                l1_action_hash(multi_sig, payload.nonce, payload.vault_address)?;
+            let send_multi_sig = SendMultiSig {
+                signature_chain_id: multi_sig.signature_chain_id,
+                hyperliquid_chain: hyperliquid_chain(is_mainnet).to_string(),
+                multi_sig_action_hash,
+                nonce: payload.nonce,
+            };
+            (
+                Some(
+                    rmp_serde::to_vec_named(multi_sig)
+                        .map_err(|e| Error::RmpParse(e.to_string()))?,
+                ),
+                Some(multi_sig_action_hash),
+                send_multi_sig.eip712_signing_hash(),
+            )
+        }
+        action if is_l1_action => {
+            let connection_id = action.hash(payload.nonce, payload.vault_address)?;
+            (
+                Some(rmp_serde::to_vec_named(action).map_err(|e| Error::RmpParse(e.to_string()))?),
+                Some(connection_id),
+                l1_action_signing_hash(connection_id, is_mainnet),
+            )
+        }
+        action => (None, None, action.extract_eip712_hash()?),
+    };
+
+    let signature = payload
+        .signature
+        .map(WireSignature::into_signature)
+        .transpose()?;
+    let signer =
+        signature.and_then(|signature| signature.recover_address_from_prehash(&signing_hash).ok());
+
+    Ok(DecodedPayload {
+        action,
+        action_json: payload.action,
+        nonce: payload.nonce,
+        vault_address: payload.vault_address,
+        signature,
+        is_l1_action,
+        msgpack,
+        connection_id,
+        signing_hash,
+        signer,
+        round_trips,
+    })
+}
+
+impl fmt::Display for DecodedPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match (&self.action, self.is_l1_action) {
+            (ActionKind::MultiSig(_), _) => "multi-sig",
+            (_, true) => "L1",
+            (_, false) => "user-signed",
+        };
+        let action_type = self.action_json["type"].as_str().unwrap_or("?");
+        let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+
+        writeln!(f, "action:        {action_type} ({kind})")?;
+        writeln!(f, "nonce:         {}", self.nonce)?;
+        writeln!(
+            f,
+            "vault:         {}",
+            or_none(self.vault_address.map(|vault| vault.to_string()))
+        )?;
+        if let Some(msgpack) = &self.msgpack {
+            writeln!(
+                f,
+                "msgpack:       {} ({} bytes)",
+                hex::encode(msgpack),
+                msgpack.len()
+            )?;
+        }
+        if let Some(connection_id) = &self.connection_id {
+            writeln!(f, "connection id: {connection_id}")?;
+        }
+        writeln!(f, "signing hash:  {}", self.signing_hash)?;
+        writeln!(
+            f,
+            "signature:     {}",
+            or_none(self.signature.map(|signature| signature.to_string()))
+        )?;
+        match (self.signer, self.signature) {
+            (Some(signer), _) => writeln!(f, "signer:        {signer}")?,
+            (None, Some(_)) => writeln!(f, "signer:        unrecoverable")?,
+            (None, None) => writeln!(f, "signer:        none")?,
+        }
+        if !self.round_trips {
+            writeln!(
+                f,
+                "warning:       the action does not serialize back to the payload, \
+                 the sender hashed a different encoding"
+            )?;
+        }
+        write!(
+            f,
+            "action json:\n{}",
+            serde_json::to_string_pretty(&self.action_json).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::signers::local::PrivateKeySigner;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        BaseUrl, ExchangeClient,
+        exchange::{
+            builder::BuildAction,
+            requests::{PerpDeploy, SetFeeScale, UsdSend},
+        },
+    };
+
+    fn payload(action: ActionKind, wallet: &PrivateKeySigner, vault: Option<Address>) -> String {
+        let client = ExchangeClient::new(Some(BaseUrl::Testnet), vault, HashMap::new()).unwrap();
+        let signed = action.build(&client).unwrap().sign(wallet).unwrap();
+        json!({
+            "action": signed.action,
+            "nonce": signed.nonce,
+            "signature": {
+                "r": signed.signature.r(),
+                "s": signed.signature.s(),
+                "v": 27 + signed.signature.v() as u64,
+            },
+            "vaultAddress": signed.vault_address,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_decode_l1_action() -> Result<()> {
+        let wallet = PrivateKeySigner::random();
+        let vault = Address::repeat_byte(0x11);
+        let action = ActionKind::PerpDeploy(PerpDeploy::SetFeeScale(SetFeeScale {
+            dex: "test".to_string(),
+            scale: "0.5".to_string(),
+        }));
+
+        let decoded = decode_payload(&payload(action.clone(), &wallet, Some(vault)), false)?;
+        assert!(matches!(
+            decoded.action,
+            ActionKind::PerpDeploy(PerpDeploy::SetFeeScale(_))
+        ));
+        assert!(decoded.round_trips);
+        assert_eq!(
+            decoded.connection_id,
+            Some(action.hash(decoded.nonce, Some(vault))?)
+        );
+        assert_eq!(decoded.signer, Some(wallet.address()));
+
+        // Decoding as mainnet recovers someone else.
+        let decoded = decode_payload(&payload(action, &wallet, Some(vault)), true)?;
+        assert_ne!(decoded.signer, Some(wallet.address()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_user_signed_action() -> Result<()> {
+        let wallet = PrivateKeySigner::random();
+        let action = ActionKind::UsdSend(UsdSend {
+            signature_chain_id: 421614,
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        });
+
+        let decoded = decode_payload(&payload(action, &wallet, None), false)?;
+        assert!(!decoded.is_l1_action);
+        assert_eq!(decoded.msgpack, None);
+        assert_eq!(decoded.signer, Some(wallet.address()));
+        assert!(decoded.to_string().contains("usdSend (user-signed)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_flags_reordered_action() -> Result<()> {
+        let payload = json!({
+            "action": { "type": "updateLeverage", "leverage": 10, "asset": 1, "isCross": true },
+            "nonce": 1,
+            "signature": null,
+            "vaultAddress": null,
+        });
+
+        let decoded = decode_payload(&payload.to_string(), true)?;
+        assert!(!decoded.round_trips);
+        assert_eq!(decoded.signer, None);
+        assert!(decoded.to_string().contains("warning"));
+        Ok(())
+    }
+}
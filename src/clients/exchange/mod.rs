@@ -1,11 +1,21 @@
 mod action;
 mod action_kind;
 mod client;
+mod custom_action;
+mod decode;
+mod multi_sig;
 
 pub mod builder;
 pub mod requests;
 pub mod responses;
 
-pub use action::{Action, SignedAction, SigningData};
+pub(crate) use action::WireSignature;
+pub use action::{Action, ActionSigner, SignedAction, SigningData};
 pub use action_kind::ActionKind;
+pub(crate) use action_kind::l1_action_hash;
 pub use client::ExchangeClient;
+pub use custom_action::{
+    CustomL1Action, CustomUserSignedAction, L1Action, RawAction, UserSignedAction,
+};
+pub use decode::{DecodedPayload, decode_payload};
+pub use multi_sig::MultiSigProposal;
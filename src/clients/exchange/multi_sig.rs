@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+
+use alloy::{
+    dyn_abi::Eip712Domain,
+    primitives::{Address, B256, Signature},
+    signers::{SignerSync, local::PrivateKeySigner},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, Result,
+    consts::SIGNATURE_CHAIN_ID,
+    eip712::Eip712,
+    exchange::{
+        Action, ActionKind, ExchangeClient, SigningData,
+        action_kind::l1_action_hash,
+        builder::typed_data_action,
+        requests::{
+            MultiSig, MultiSigPayload, SendMultiSig, eip_712_domain, multi_sig_type_string,
+            typed_struct_hash,
+        },
+    },
+    utils::{hyperliquid_chain, next_nonce},
+};
+
+/// Action of a multi-sig user awaiting the signatures of its authorized
+/// users.
+///
+/// The outer signer, the authorized user who will submit the action, creates
+/// the proposal and shares it; it round-trips through JSON. Each co-signer
+/// signs [`MultiSigProposal::signing_data`] offline, or calls
+/// [`MultiSigProposal::sign`], and [`MultiSigProposal::aggregate`] turns
+/// enough signatures into the `multiSig` action the outer signer signs.
+///
+/// # Example
+/// ```no_run
+/// # use std::collections::HashMap;
+/// # use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+/// # use hl_rs::exchange::{ActionKind, ExchangeClient, MultiSigProposal, requests::UpdateLeverage};
+/// # fn main() -> hl_rs::Result<()> {
+/// # let client = ExchangeClient::new(None, None, HashMap::new())?;
+/// # let (outer, cosigner) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+/// # let multi_sig_user = Address::ZERO;
+/// let action = ActionKind::UpdateLeverage(UpdateLeverage {
+///     asset: 0,
+///     is_cross: true,
+///     leverage: 5,
+/// });
+/// let proposal = MultiSigProposal::new(&client, multi_sig_user, outer.address(), action)?;
+/// let signatures = vec![proposal.sign(&outer)?, proposal.sign(&cosigner)?];
+/// let signed = proposal.aggregate(&client, &signatures)?.sign(&outer)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposal {
+    pub multi_sig_user: Address,
+    pub outer_signer: Address,
+    pub action: ActionKind,
+    pub nonce: u64,
+    pub vault_address: Option<Address>,
+    pub is_mainnet: bool,
+}
+
+/// EIP-712 hash of a user-signed action with the multi-sig fields added.
+struct MultiSigTypedData {
+    chain_id: u64,
+    struct_hash: B256,
+}
+
+impl Eip712 for MultiSigTypedData {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        self.struct_hash
+    }
+}
+
+impl MultiSigProposal {
+    /// L1 actions get a fresh nonce; user-signed actions keep their own.
+    pub fn new(
+        client: &ExchangeClient,
+        multi_sig_user: Address,
+        outer_signer: Address,
+        action: ActionKind,
+    ) -> Result<Self> {
+        let nonce = match &action {
+            ActionKind::MultiSig(_) => {
+                return Err(Error::MultiSig(
+                    "multiSig actions cannot be nested".to_string(),
+                ));
+            }
+            action if action.is_l1_action() => next_nonce(),
+            action => action.typed_data_nonce()?,
+        };
+
+        Ok(Self {
+            multi_sig_user,
+            outer_signer,
+            action,
+            nonce,
+            vault_address: client.vault_address(),
+            is_mainnet: client.is_mainnet(),
+        })
+    }
+
+    /// What each co-signer signs: the inner action bound to the multi-sig
+    /// user and the outer signer.
+    pub fn signing_data(&self) -> Result<SigningData> {
+        let multi_sig_user = self.multi_sig_user.to_string().to_lowercase();
+        let outer_signer = self.outer_signer.to_string().to_lowercase();
+
+        if self.action.is_l1_action() {
+            let envelope = (&multi_sig_user, &outer_signer, &self.action);
+            let connection_id = l1_action_hash(&envelope, self.nonce, self.vault_address)?;
+            return Ok(SigningData::L1 {
+                connection_id,
+                is_mainnet: self.is_mainnet,
+            });
+        }
+
+        let type_string = self.action.user_signed_type().ok_or_else(|| {
+            Error::MultiSig("action type not supported in a multiSig action".to_string())
+        })?;
+        let mut values =
+            serde_json::to_value(&self.action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let chain_id = values["signatureChainId"]
+            .as_str()
+            .and_then(|id| u64::from_str_radix(id.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| Error::MultiSig("action has no signatureChainId".to_string()))?;
+        values["payloadMultiSigUser"] = multi_sig_user.into();
+        values["outerSigner"] = outer_signer.into();
+
+        let typed_data = MultiSigTypedData {
+            chain_id,
+            struct_hash: typed_struct_hash(&multi_sig_type_string(type_string)?, &values)?,
+        };
+        Ok(SigningData::TypedData {
+            hash: typed_data.eip712_signing_hash(),
+        })
+    }
+
+    /// Co-sign the proposal with `wallet`.
+    pub fn sign(&self, wallet: &PrivateKeySigner) -> Result<Signature> {
+        wallet
+            .sign_hash_sync(&self.signing_data()?.signing_hash())
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+
+    /// Address that produced `signature` over this proposal.
+    pub fn recover_signer(&self, signature: &Signature) -> Result<Address> {
+        signature
+            .recover_address_from_prehash(&self.signing_data()?.signing_hash())
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+
+    /// Assemble co-signer signatures into the `multiSig` action, which the
+    /// outer signer then signs and sends.
+    ///
+    /// Signatures must come from distinct signers. Whether they are
+    /// authorized users and meet the threshold is checked by the exchange.
+    /// The inner action must pass the client's risk checker, if any.
+    pub fn aggregate(self, client: &ExchangeClient, signatures: &[Signature]) -> Result<Action> {
+        if signatures.is_empty() {
+            return Err(Error::MultiSig("no signatures to aggregate".to_string()));
+        }
+
+        let mut signers = HashSet::new();
+        for signature in signatures {
+            let signer = self.recover_signer(signature)?;
+            if !signers.insert(signer) {
+                return Err(Error::MultiSig(format!(
+                    "duplicate signature from {signer}"
+                )));
+            }
+        }
+
+        if let Some(risk_checker) = client.risk_checker() {
+            risk_checker.lock().unwrap().check(&self.action)?;
+        }
+
+        let multi_sig = MultiSig {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            signatures: signatures.iter().map(|&sig| sig.into()).collect(),
+            payload: MultiSigPayload {
+                multi_sig_user: self.multi_sig_user.to_string().to_lowercase(),
+                outer_signer: self.outer_signer.to_string().to_lowercase(),
+                action: Box::new(self.action),
+            },
+        };
+
+        // The outer signer signs the hash of the action without its type.
+        let send_multi_sig = SendMultiSig {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            hyperliquid_chain: hyperliquid_chain(self.is_mainnet).to_string(),
+            multi_sig_action_hash: l1_action_hash(&multi_sig, self.nonce, self.vault_address)?,
+            nonce: self.nonce,
+        };
+
+        typed_data_action(
+            &ActionKind::MultiSig(multi_sig),
+            send_multi_sig.eip712_signing_hash(),
+            client,
+            self.nonce,
+            self.vault_address,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::exchange::requests::{UpdateLeverage, UsdSend};
+
+    fn proposal(client: &ExchangeClient, outer: Address, action: ActionKind) -> MultiSigProposal {
+        MultiSigProposal::new(client, Address::repeat_byte(0x42), outer, action).unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_l1_action() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let (outer, cosigner) = (PrivateKeySigner::random(), PrivateKeySigner::random());
+        let proposal = proposal(
+            &client,
+            outer.address(),
+            ActionKind::UpdateLeverage(UpdateLeverage {
+                asset: 0,
+                is_cross: true,
+                leverage: 5,
+            }),
+        );
+
+        // Co-signers may receive the proposal as JSON.
+        let json = serde_json::to_string(&proposal).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let shared: MultiSigProposal =
+            serde_json::from_str(&json).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let signature = shared.sign(&cosigner)?;
+        assert_eq!(proposal.recover_signer(&signature)?, cosigner.address());
+
+        let signatures = [proposal.sign(&outer)?, signature];
+        assert!(
+            proposal
+                .clone()
+                .aggregate(&client, &[signature, signature])
+                .is_err()
+        );
+
+        let nonce = proposal.nonce;
+        let action = proposal.aggregate(&client, &signatures)?;
+        assert_eq!(action.nonce, nonce);
+        assert_eq!(action.action["type"], "multiSig");
+        assert_eq!(action.action["signatureChainId"], "0x66eee");
+        assert_eq!(action.action["payload"]["action"]["type"], "updateLeverage");
+        assert_eq!(
+            action.action["signatures"][1]["v"],
+            27 + signature.v() as u64
+        );
+        assert!(matches!(
+            action.signing_data(),
+            SigningData::TypedData { .. }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_signed_action_binds_multi_sig_fields() -> Result<()> {
+        let client = ExchangeClient::new(None, None, HashMap::new())?;
+        let usd_send = UsdSend {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            hyperliquid_chain: "Mainnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        };
+        let proposal = proposal(
+            &client,
+            Address::repeat_byte(0x01),
+            ActionKind::UsdSend(usd_send.clone()),
+        );
+        assert_eq!(proposal.nonce, usd_send.time);
+
+        let hash = proposal.signing_data()?.signing_hash();
+        assert_ne!(hash, usd_send.eip712_signing_hash());
+
+        let other_outer = MultiSigProposal {
+            outer_signer: Address::repeat_byte(0x02),
+            ..proposal
+        };
+        assert_ne!(hash, other_outer.signing_data()?.signing_hash());
+        Ok(())
+    }
+}
@@ -3,20 +3,29 @@ use alloy::{
     primitives::{Address, B256, keccak256},
     sol_types::{SolValue, eip712_domain},
 };
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     eip712::Eip712,
     exchange::requests::{
-        BuilderInfo, CancelRequest, CancelRequestCloid, ModifyRequest, OrderRequest,
+        BuilderInfo, CancelRequest, CancelRequestCloid, ModifyRequest, OrderRequest, TwapRequest,
     },
 };
 
+mod multi_sig;
 mod perp_deploy;
+mod spot_deploy;
+mod sub_account;
+mod vault;
 
+pub use multi_sig::*;
 pub use perp_deploy::*;
+pub use spot_deploy::*;
+pub use sub_account::*;
+pub use vault::*;
 
-fn eip_712_domain(chain_id: u64) -> Eip712Domain {
+/// Domain shared by all user-signed `HyperliquidTransaction:*` actions.
+pub fn eip_712_domain(chain_id: u64) -> Eip712Domain {
     eip712_domain! {
         name: "HyperliquidSignTransaction",
         version: "1",
@@ -25,6 +34,19 @@ fn eip_712_domain(chain_id: u64) -> Eip712Domain {
     }
 }
 
+// EIP-712 type strings of the user-signed actions.
+pub(crate) const USD_SEND_TYPE: &str = "HyperliquidTransaction:UsdSend(string hyperliquidChain,string destination,string amount,uint64 time)";
+pub(crate) const APPROVE_AGENT_TYPE: &str = "HyperliquidTransaction:ApproveAgent(string hyperliquidChain,address agentAddress,string agentName,uint64 nonce)";
+pub(crate) const WITHDRAW_TYPE: &str = "HyperliquidTransaction:Withdraw(string hyperliquidChain,string destination,string amount,uint64 time)";
+pub(crate) const SPOT_SEND_TYPE: &str = "HyperliquidTransaction:SpotSend(string hyperliquidChain,string destination,string token,string amount,uint64 time)";
+pub(crate) const SEND_ASSET_TYPE: &str = "HyperliquidTransaction:SendAsset(string hyperliquidChain,string destination,string sourceDex,string destinationDex,string token,string amount,string fromSubAccount,uint64 nonce)";
+pub(crate) const APPROVE_BUILDER_FEE_TYPE: &str = "HyperliquidTransaction:ApproveBuilderFee(string hyperliquidChain,string maxFeeRate,address builder,uint64 nonce)";
+pub(crate) const C_DEPOSIT_TYPE: &str =
+    "HyperliquidTransaction:CDeposit(string hyperliquidChain,uint64 wei,uint64 nonce)";
+pub(crate) const C_WITHDRAW_TYPE: &str =
+    "HyperliquidTransaction:CWithdraw(string hyperliquidChain,uint64 wei,uint64 nonce)";
+pub(crate) const TOKEN_DELEGATE_TYPE: &str = "HyperliquidTransaction:TokenDelegate(string hyperliquidChain,address validator,uint64 wei,bool isUndelegate,uint64 nonce)";
+
 fn serialize_hex<S>(val: &u64, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -32,10 +54,40 @@ where
     s.serialize_str(&format!("0x{val:x}"))
 }
 
+/// Accepts the `"0x66eee"` form written by [`serialize_hex`] as well as a
+/// plain number.
+fn deserialize_hex<'de, D>(d: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HexOrNumber {
+        Hex(String),
+        Number(u64),
+    }
+
+    match HexOrNumber::deserialize(d)? {
+        HexOrNumber::Number(n) => Ok(n),
+        HexOrNumber::Hex(s) => {
+            u64::from_str_radix(s.trim_start_matches("0x"), 16).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+/// msgpack would encode an `Address` as raw bytes, but the exchange hashes
+/// addresses in L1 actions as lowercase hex strings.
+fn serialize_address<S>(address: &Address, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&address.to_string().to_lowercase())
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UsdSend {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -50,9 +102,7 @@ impl Eip712 for UsdSend {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:UsdSend(string hyperliquidChain,string destination,string amount,uint64 time)",
-            ),
+            keccak256(USD_SEND_TYPE),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.amount),
@@ -99,6 +149,20 @@ pub struct BulkModify {
     pub modifies: Vec<ModifyRequest>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapOrder {
+    pub twap: TwapRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapCancel {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "t", alias = "twapId")]
+    pub twap_id: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkCancelCloid {
@@ -108,7 +172,7 @@ pub struct BulkCancelCloid {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveAgent {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub agent_address: Address,
@@ -123,9 +187,7 @@ impl Eip712 for ApproveAgent {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:ApproveAgent(string hyperliquidChain,address agentAddress,string agentName,uint64 nonce)",
-            ),
+            keccak256(APPROVE_AGENT_TYPE),
             keccak256(&self.hyperliquid_chain),
             &self.agent_address,
             keccak256(self.agent_name.as_deref().unwrap_or("")),
@@ -138,7 +200,7 @@ impl Eip712 for ApproveAgent {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Withdraw3 {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -153,9 +215,7 @@ impl Eip712 for Withdraw3 {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:Withdraw(string hyperliquidChain,string destination,string amount,uint64 time)",
-            ),
+            keccak256(WITHDRAW_TYPE),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.amount),
@@ -168,7 +228,7 @@ impl Eip712 for Withdraw3 {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SpotSend {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -184,9 +244,7 @@ impl Eip712 for SpotSend {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:SpotSend(string hyperliquidChain,string destination,string token,string amount,uint64 time)",
-            ),
+            keccak256(SPOT_SEND_TYPE),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.token),
@@ -213,7 +271,7 @@ pub struct ClassTransfer {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SendAsset {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub destination: String,
@@ -232,9 +290,7 @@ impl Eip712 for SendAsset {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:SendAsset(string hyperliquidChain,string destination,string sourceDex,string destinationDex,string token,string amount,string fromSubAccount,uint64 nonce)",
-            ),
+            keccak256(SEND_ASSET_TYPE),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.destination),
             keccak256(&self.source_dex),
@@ -251,6 +307,7 @@ impl Eip712 for SendAsset {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VaultTransfer {
+    #[serde(serialize_with = "serialize_address")]
     pub vault_address: Address,
     pub is_deposit: bool,
     pub usd: u64,
@@ -271,7 +328,7 @@ pub struct EvmUserModify {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveBuilderFee {
-    #[serde(serialize_with = "serialize_hex")]
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
     pub signature_chain_id: u64,
     pub hyperliquid_chain: String,
     pub builder: Address,
@@ -290,6 +347,11 @@ pub struct ScheduleCancel {
 #[serde(rename_all = "camelCase")]
 pub struct ClaimRewards;
 
+/// Does nothing but consume its nonce.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Noop;
+
 impl Eip712 for ApproveBuilderFee {
     fn domain(&self) -> Eip712Domain {
         eip_712_domain(self.signature_chain_id)
@@ -297,9 +359,7 @@ impl Eip712 for ApproveBuilderFee {
 
     fn struct_hash(&self) -> B256 {
         let items = (
-            keccak256(
-                "HyperliquidTransaction:ApproveBuilderFee(string hyperliquidChain,string maxFeeRate,address builder,uint64 nonce)",
-            ),
+            keccak256(APPROVE_BUILDER_FEE_TYPE),
             keccak256(&self.hyperliquid_chain),
             keccak256(&self.max_fee_rate),
             &self.builder,
@@ -308,3 +368,87 @@ impl Eip712 for ApproveBuilderFee {
         keccak256(items.abi_encode())
     }
 }
+
+/// Moves `wei` of HYPE from the spot balance into the staking balance.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CDeposit {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl Eip712 for CDeposit {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(C_DEPOSIT_TYPE),
+            keccak256(&self.hyperliquid_chain),
+            &self.wei,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+}
+
+/// Moves `wei` of HYPE from the staking balance back into the spot balance.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CWithdraw {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl Eip712 for CWithdraw {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(C_WITHDRAW_TYPE),
+            keccak256(&self.hyperliquid_chain),
+            &self.wei,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDelegate {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub validator: Address,
+    pub wei: u64,
+    pub is_undelegate: bool,
+    pub nonce: u64,
+}
+
+impl Eip712 for TokenDelegate {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(TOKEN_DELEGATE_TYPE),
+            keccak256(&self.hyperliquid_chain),
+            &self.validator,
+            &self.wei,
+            self.is_undelegate,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+}
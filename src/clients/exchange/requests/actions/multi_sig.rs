@@ -0,0 +1,281 @@
+use alloy::{
+    dyn_abi::Eip712Domain,
+    primitives::{Address, B256, U256, keccak256},
+    signers::Signature,
+    sol_types::SolValue,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{deserialize_hex, eip_712_domain, serialize_hex};
+use crate::{Error, Result, eip712::Eip712, exchange::ActionKind};
+
+pub(crate) const CONVERT_TO_MULTI_SIG_USER_TYPE: &str = "HyperliquidTransaction:ConvertToMultiSigUser(string hyperliquidChain,string signers,uint64 nonce)";
+pub(crate) const SEND_MULTI_SIG_TYPE: &str = "HyperliquidTransaction:SendMultiSig(string hyperliquidChain,bytes32 multiSigActionHash,uint64 nonce)";
+
+/// Converts the signing account into a multi-sig user controlled by
+/// `signers`, or back into a regular user when `signers` is `"null"`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertToMultiSigUser {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    /// JSON encoded [`MultiSigSigners`].
+    pub signers: String,
+    pub nonce: u64,
+}
+
+impl Eip712 for ConvertToMultiSigUser {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(CONVERT_TO_MULTI_SIG_USER_TYPE),
+            keccak256(&self.hyperliquid_chain),
+            keccak256(&self.signers),
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigSigners {
+    pub authorized_users: Vec<String>,
+    pub threshold: u32,
+}
+
+impl MultiSigSigners {
+    /// Authorized users are sorted lowercase addresses, as the exchange
+    /// expects them.
+    pub fn new(authorized_users: &[Address], threshold: u32) -> Result<Self> {
+        if threshold == 0 || threshold as usize > authorized_users.len() {
+            return Err(Error::MultiSig(format!(
+                "threshold {threshold} out of range for {} authorized users",
+                authorized_users.len()
+            )));
+        }
+
+        let mut authorized_users: Vec<String> = authorized_users
+            .iter()
+            .map(|user| user.to_string().to_lowercase())
+            .collect();
+        authorized_users.sort();
+        authorized_users.dedup();
+        if threshold as usize > authorized_users.len() {
+            return Err(Error::MultiSig("duplicate authorized users".to_string()));
+        }
+
+        Ok(Self {
+            authorized_users,
+            threshold,
+        })
+    }
+
+    /// Value of [`ConvertToMultiSigUser::signers`].
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+}
+
+/// Co-signer signature in the form embedded in a `multiSig` action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MultiSigSignature {
+    pub r: String,
+    pub s: String,
+    pub v: u64,
+}
+
+impl From<Signature> for MultiSigSignature {
+    fn from(sig: Signature) -> Self {
+        Self {
+            r: format!("{:#x}", sig.r()),
+            s: format!("{:#x}", sig.s()),
+            v: 27 + sig.v() as u64,
+        }
+    }
+}
+
+impl TryFrom<&MultiSigSignature> for Signature {
+    type Error = Error;
+
+    fn try_from(sig: &MultiSigSignature) -> Result<Self> {
+        let parse = |hex: &str| {
+            U256::from_str_radix(hex.trim_start_matches("0x"), 16)
+                .map_err(|e| Error::GenericParse(e.to_string()))
+        };
+        let parity = match sig.v {
+            27 | 0 => false,
+            28 | 1 => true,
+            v => return Err(Error::GenericParse(format!("invalid signature v {v}"))),
+        };
+        Ok(Signature::new(parse(&sig.r)?, parse(&sig.s)?, parity))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigPayload {
+    /// Lowercase address of the multi-sig user.
+    pub multi_sig_user: String,
+    /// Lowercase address of the authorized user submitting the action.
+    pub outer_signer: String,
+    pub action: Box<ActionKind>,
+}
+
+/// Inner action signed by enough authorized users of `payload.multi_sig_user`.
+///
+/// Built with [`MultiSigProposal::aggregate`](crate::exchange::MultiSigProposal::aggregate)
+/// since the outer signer signs a hash of the whole action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSig {
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub signature_chain_id: u64,
+    pub signatures: Vec<MultiSigSignature>,
+    pub payload: MultiSigPayload,
+}
+
+/// Envelope the outer signer signs for a `multiSig` action.
+#[derive(Debug, Clone)]
+pub struct SendMultiSig {
+    pub signature_chain_id: u64,
+    pub hyperliquid_chain: String,
+    pub multi_sig_action_hash: B256,
+    pub nonce: u64,
+}
+
+impl Eip712 for SendMultiSig {
+    fn domain(&self) -> Eip712Domain {
+        eip_712_domain(self.signature_chain_id)
+    }
+
+    fn struct_hash(&self) -> B256 {
+        let items = (
+            keccak256(SEND_MULTI_SIG_TYPE),
+            keccak256(&self.hyperliquid_chain),
+            &self.multi_sig_action_hash,
+            &self.nonce,
+        );
+        keccak256(items.abi_encode())
+    }
+}
+
+/// Type string of a user-signed action with the `payloadMultiSigUser` and
+/// `outerSigner` fields co-signers sign on top of the action's own fields.
+pub(crate) fn multi_sig_type_string(type_string: &str) -> Result<String> {
+    let (head, rest) = type_string
+        .split_once(',')
+        .ok_or_else(|| Error::Eip712(format!("unexpected type string {type_string}")))?;
+    Ok(format!(
+        "{head},address payloadMultiSigUser,address outerSigner,{rest}"
+    ))
+}
+
+/// EIP-712 struct hash of `values` for a type string such as
+/// `Name(string a,uint64 b)`, reading each field from the JSON object.
+/// A missing or null string field hashes as the empty string.
+pub(crate) fn typed_struct_hash(type_string: &str, values: &Value) -> Result<B256> {
+    let fields = type_string
+        .split_once('(')
+        .and_then(|(_, fields)| fields.strip_suffix(')'))
+        .ok_or_else(|| Error::Eip712(format!("unexpected type string {type_string}")))?;
+
+    let mut encoded = keccak256(type_string).to_vec();
+    for field in fields.split(',') {
+        let (ty, name) = field
+            .split_once(' ')
+            .ok_or_else(|| Error::Eip712(format!("unexpected field {field}")))?;
+        let value = &values[name];
+        let invalid = || Error::Eip712(format!("invalid {ty} value for {name}: {value}"));
+
+        let word: B256 = match ty {
+            "string" => keccak256(value.as_str().unwrap_or_default()),
+            "address" => value
+                .as_str()
+                .and_then(|s| s.parse::<Address>().ok())
+                .ok_or_else(invalid)?
+                .into_word(),
+            "uint64" => U256::from(value.as_u64().ok_or_else(invalid)?).into(),
+            "bool" => U256::from(value.as_bool().ok_or_else(invalid)? as u8).into(),
+            "bytes32" => value
+                .as_str()
+                .and_then(|s| s.parse::<B256>().ok())
+                .ok_or_else(invalid)?,
+            _ => return Err(Error::Eip712(format!("unsupported field type {ty}"))),
+        };
+        encoded.extend_from_slice(word.as_slice());
+    }
+
+    Ok(keccak256(encoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::requests::{TOKEN_DELEGATE_TYPE, TokenDelegate, USD_SEND_TYPE, UsdSend};
+
+    #[test]
+    fn test_typed_struct_hash_matches_eip712_impls() -> Result<()> {
+        let usd_send = UsdSend {
+            signature_chain_id: 421614,
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        };
+        let values =
+            serde_json::to_value(&usd_send).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(
+            typed_struct_hash(USD_SEND_TYPE, &values)?,
+            usd_send.struct_hash()
+        );
+
+        let token_delegate = TokenDelegate {
+            signature_chain_id: 421614,
+            hyperliquid_chain: "Testnet".to_string(),
+            validator: Address::repeat_byte(0x11),
+            wei: 100,
+            is_undelegate: true,
+            nonce: 1,
+        };
+        let values =
+            serde_json::to_value(&token_delegate).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(
+            typed_struct_hash(TOKEN_DELEGATE_TYPE, &values)?,
+            token_delegate.struct_hash()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_sig_type_string() -> Result<()> {
+        assert_eq!(
+            multi_sig_type_string(USD_SEND_TYPE)?,
+            "HyperliquidTransaction:UsdSend(string hyperliquidChain,address payloadMultiSigUser,address outerSigner,string destination,string amount,uint64 time)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_sig_signers() -> Result<()> {
+        let a = Address::repeat_byte(0xbb);
+        let b = Address::repeat_byte(0xaa);
+        let signers = MultiSigSigners::new(&[a, b], 2)?;
+        assert_eq!(
+            signers.to_json()?,
+            format!(
+                r#"{{"authorizedUsers":["{}","{}"],"threshold":2}}"#,
+                b.to_string().to_lowercase(),
+                a.to_string().to_lowercase()
+            )
+        );
+        assert!(MultiSigSigners::new(&[a, a], 2).is_err());
+        assert!(MultiSigSigners::new(&[a], 0).is_err());
+        Ok(())
+    }
+}
@@ -41,7 +41,7 @@ pub struct SetOracle {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(transparent)]
 pub struct SetFundingMultipliers {
     pub multipliers: Vec<(String, String)>,
 }
@@ -54,7 +54,7 @@ pub struct HaltTrading {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(transparent)]
 pub struct SetMarginTableIds {
     pub ids: Vec<(String, u32)>,
 }
@@ -67,7 +67,7 @@ pub struct SetFeeRecipient {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(transparent)]
 pub struct SetOpenInterestCaps {
     pub caps: Vec<(String, String)>,
 }
@@ -93,8 +93,46 @@ pub struct InsertMarginTable {
     pub margin_table: RawMarginTable,
 }
 
-/// Wrapper that serializes with type: "perpDeploy"
-/// and one of the action fields (registerAsset, setOracle, etc.)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubDeployerInput {
+    /// Name of the perpDeploy action being delegated, e.g. "setOracle".
+    pub variant: String,
+    pub user: String,
+    pub allowed: bool,
+}
+
+/// Grants or revokes sub-deployers' permission to run perpDeploy actions on
+/// behalf of the dex deployer.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSubDeployers {
+    pub dex: String,
+    pub sub_deployers: Vec<SubDeployerInput>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct SetMarginModes {
+    /// Coin and margin mode ("strictIsolated" or "noCross").
+    pub modes: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFeeScale {
+    pub dex: String,
+    pub scale: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct SetGrowthModes {
+    pub modes: Vec<(String, bool)>,
+}
+
+/// Wrapper that serializes one of the perpDeploy action fields
+/// (registerAsset, setOracle, etc.); `ActionKind` adds type: "perpDeploy"
 #[derive(Debug, Clone)]
 pub enum PerpDeploy {
     RegisterAsset(RegisterAsset),
@@ -105,6 +143,10 @@ pub enum PerpDeploy {
     SetFeeRecipient(SetFeeRecipient),
     SetOpenInterestCaps(SetOpenInterestCaps),
     InsertMarginTable(InsertMarginTable),
+    SetSubDeployers(SetSubDeployers),
+    SetMarginModes(SetMarginModes),
+    SetFeeScale(SetFeeScale),
+    SetGrowthModes(SetGrowthModes),
 }
 
 impl Serialize for PerpDeploy {
@@ -113,8 +155,7 @@ impl Serialize for PerpDeploy {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("PerpDeploy", 2)?;
-        state.serialize_field("type", "perpDeploy")?;
+        let mut state = serializer.serialize_struct("PerpDeploy", 1)?;
         match self {
             PerpDeploy::RegisterAsset(v) => state.serialize_field("registerAsset", v)?,
             PerpDeploy::SetOracle(v) => state.serialize_field("setOracle", v)?,
@@ -128,6 +169,10 @@ impl Serialize for PerpDeploy {
                 state.serialize_field("setOpenInterestCaps", v)?;
             }
             PerpDeploy::InsertMarginTable(v) => state.serialize_field("insertMarginTable", v)?,
+            PerpDeploy::SetSubDeployers(v) => state.serialize_field("setSubDeployers", v)?,
+            PerpDeploy::SetMarginModes(v) => state.serialize_field("setMarginModes", v)?,
+            PerpDeploy::SetFeeScale(v) => state.serialize_field("setFeeScale", v)?,
+            PerpDeploy::SetGrowthModes(v) => state.serialize_field("setGrowthModes", v)?,
         }
         state.end()
     }
@@ -163,6 +208,10 @@ impl<'de> Deserialize<'de> for PerpDeploy {
                 let mut set_fee_recipient: Option<SetFeeRecipient> = None;
                 let mut set_open_interest_caps: Option<SetOpenInterestCaps> = None;
                 let mut insert_margin_table: Option<InsertMarginTable> = None;
+                let mut set_sub_deployers: Option<SetSubDeployers> = None;
+                let mut set_margin_modes: Option<SetMarginModes> = None;
+                let mut set_fee_scale: Option<SetFeeScale> = None;
+                let mut set_growth_modes: Option<SetGrowthModes> = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -193,15 +242,31 @@ impl<'de> Deserialize<'de> for PerpDeploy {
                         "insertMarginTable" => {
                             insert_margin_table = Some(map.next_value()?);
                         }
+                        "setSubDeployers" => {
+                            set_sub_deployers = Some(map.next_value()?);
+                        }
+                        "setMarginModes" => {
+                            set_margin_modes = Some(map.next_value()?);
+                        }
+                        "setFeeScale" => {
+                            set_fee_scale = Some(map.next_value()?);
+                        }
+                        "setGrowthModes" => {
+                            set_growth_modes = Some(map.next_value()?);
+                        }
                         _ => {
                             let _ = map.next_value::<serde::de::IgnoredAny>()?;
                         }
                     }
                 }
 
-                if type_val.as_deref() != Some("perpDeploy") {
+                // The tag is consumed by `ActionKind`, but accept it when a
+                // perpDeploy action is deserialized on its own.
+                if let Some(type_val) = type_val
+                    && type_val != "perpDeploy"
+                {
                     return Err(de::Error::invalid_value(
-                        serde::de::Unexpected::Str(type_val.unwrap_or_default().as_str()),
+                        serde::de::Unexpected::Str(type_val.as_str()),
                         &"perpDeploy",
                     ));
                 }
@@ -222,6 +287,14 @@ impl<'de> Deserialize<'de> for PerpDeploy {
                     Ok(PerpDeploy::SetOpenInterestCaps(v))
                 } else if let Some(v) = insert_margin_table {
                     Ok(PerpDeploy::InsertMarginTable(v))
+                } else if let Some(v) = set_sub_deployers {
+                    Ok(PerpDeploy::SetSubDeployers(v))
+                } else if let Some(v) = set_margin_modes {
+                    Ok(PerpDeploy::SetMarginModes(v))
+                } else if let Some(v) = set_fee_scale {
+                    Ok(PerpDeploy::SetFeeScale(v))
+                } else if let Some(v) = set_growth_modes {
+                    Ok(PerpDeploy::SetGrowthModes(v))
                 } else {
                     Err(de::Error::missing_field(
                         "one of the perpDeploy action fields",
@@ -233,3 +306,49 @@ impl<'de> Deserialize<'de> for PerpDeploy {
         deserializer.deserialize_map(PerpDeployVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::ActionKind;
+
+    #[test]
+    fn test_perp_deploy_round_trip() -> serde_json::Result<()> {
+        let action = ActionKind::PerpDeploy(PerpDeploy::SetSubDeployers(SetSubDeployers {
+            dex: "test".to_string(),
+            sub_deployers: vec![SubDeployerInput {
+                variant: "setOracle".to_string(),
+                user: "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+                allowed: true,
+            }],
+        }));
+
+        let json = serde_json::to_string(&action)?;
+        assert_eq!(
+            json,
+            r#"{"type":"perpDeploy","setSubDeployers":{"dex":"test","subDeployers":[{"variant":"setOracle","user":"0x0d1d9635d0640821d15e323ac8adadfa9c111414","allowed":true}]}}"#
+        );
+
+        let ActionKind::PerpDeploy(PerpDeploy::SetSubDeployers(set_sub_deployers)) =
+            serde_json::from_str(&json)?
+        else {
+            panic!("expected a setSubDeployers action");
+        };
+        assert_eq!(set_sub_deployers.sub_deployers[0].variant, "setOracle");
+        Ok(())
+    }
+
+    #[test]
+    fn test_perp_deploy_list_variants_are_bare_arrays() -> serde_json::Result<()> {
+        let action =
+            ActionKind::PerpDeploy(PerpDeploy::SetFundingMultipliers(SetFundingMultipliers {
+                multipliers: vec![("test:ABC".to_string(), "1.5".to_string())],
+            }));
+
+        assert_eq!(
+            serde_json::to_string(&action)?,
+            r#"{"type":"perpDeploy","setFundingMultipliers":[["test:ABC","1.5"]]}"#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSpec {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterToken2 {
+    pub spec: TokenSpec,
+    pub max_gas: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGenesis {
+    pub token: u32,
+    /// Lowercase user addresses and their genesis balance in wei.
+    pub user_and_wei: Vec<(String, String)>,
+    /// Holders of an existing token index receive a genesis balance in wei.
+    pub existing_token_and_wei: Vec<(u32, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    pub token: u32,
+    pub max_supply: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_hyperliquidity: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterSpot {
+    /// Base and quote token indices.
+    pub tokens: (u32, u32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterHyperliquidity {
+    pub spot: u32,
+    pub start_px: String,
+    pub order_sz: String,
+    pub n_orders: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_seeded_levels: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetDeployerTradingFeeShare {
+    pub token: u32,
+    pub share: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnableFreezePrivilege {
+    pub token: u32,
+}
+
+/// Wrapper that serializes one of the spotDeploy action fields
+/// (registerToken2, genesis, etc.); `ActionKind` adds type: "spotDeploy"
+#[derive(Debug, Clone)]
+pub enum SpotDeploy {
+    RegisterToken2(RegisterToken2),
+    UserGenesis(UserGenesis),
+    Genesis(Genesis),
+    RegisterSpot(RegisterSpot),
+    RegisterHyperliquidity(RegisterHyperliquidity),
+    SetDeployerTradingFeeShare(SetDeployerTradingFeeShare),
+    EnableFreezePrivilege(EnableFreezePrivilege),
+}
+
+impl Serialize for SpotDeploy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("SpotDeploy", 1)?;
+        match self {
+            SpotDeploy::RegisterToken2(v) => state.serialize_field("registerToken2", v)?,
+            SpotDeploy::UserGenesis(v) => state.serialize_field("userGenesis", v)?,
+            SpotDeploy::Genesis(v) => state.serialize_field("genesis", v)?,
+            SpotDeploy::RegisterSpot(v) => state.serialize_field("registerSpot", v)?,
+            SpotDeploy::RegisterHyperliquidity(v) => {
+                state.serialize_field("registerHyperliquidity", v)?;
+            }
+            SpotDeploy::SetDeployerTradingFeeShare(v) => {
+                state.serialize_field("setDeployerTradingFeeShare", v)?;
+            }
+            SpotDeploy::EnableFreezePrivilege(v) => {
+                state.serialize_field("enableFreezePrivilege", v)?;
+            }
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SpotDeploy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+        use std::fmt;
+
+        struct SpotDeployVisitor;
+
+        impl<'de> Visitor<'de> for SpotDeployVisitor {
+            type Value = SpotDeploy;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a spotDeploy action")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut type_val: Option<String> = None;
+                let mut register_token2: Option<RegisterToken2> = None;
+                let mut user_genesis: Option<UserGenesis> = None;
+                let mut genesis: Option<Genesis> = None;
+                let mut register_spot: Option<RegisterSpot> = None;
+                let mut register_hyperliquidity: Option<RegisterHyperliquidity> = None;
+                let mut set_deployer_trading_fee_share: Option<SetDeployerTradingFeeShare> = None;
+                let mut enable_freeze_privilege: Option<EnableFreezePrivilege> = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "type" => {
+                            type_val = Some(map.next_value()?);
+                        }
+                        "registerToken2" => {
+                            register_token2 = Some(map.next_value()?);
+                        }
+                        "userGenesis" => {
+                            user_genesis = Some(map.next_value()?);
+                        }
+                        "genesis" => {
+                            genesis = Some(map.next_value()?);
+                        }
+                        "registerSpot" => {
+                            register_spot = Some(map.next_value()?);
+                        }
+                        "registerHyperliquidity" => {
+                            register_hyperliquidity = Some(map.next_value()?);
+                        }
+                        "setDeployerTradingFeeShare" => {
+                            set_deployer_trading_fee_share = Some(map.next_value()?);
+                        }
+                        "enableFreezePrivilege" => {
+                            enable_freeze_privilege = Some(map.next_value()?);
+                        }
+                        _ => {
+                            let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                // The tag is consumed by `ActionKind`, but accept it when a
+                // spotDeploy action is deserialized on its own.
+                if let Some(type_val) = type_val
+                    && type_val != "spotDeploy"
+                {
+                    return Err(de::Error::invalid_value(
+                        serde::de::Unexpected::Str(type_val.as_str()),
+                        &"spotDeploy",
+                    ));
+                }
+
+                if let Some(v) = register_token2 {
+                    Ok(SpotDeploy::RegisterToken2(v))
+                } else if let Some(v) = user_genesis {
+                    Ok(SpotDeploy::UserGenesis(v))
+                } else if let Some(v) = genesis {
+                    Ok(SpotDeploy::Genesis(v))
+                } else if let Some(v) = register_spot {
+                    Ok(SpotDeploy::RegisterSpot(v))
+                } else if let Some(v) = register_hyperliquidity {
+                    Ok(SpotDeploy::RegisterHyperliquidity(v))
+                } else if let Some(v) = set_deployer_trading_fee_share {
+                    Ok(SpotDeploy::SetDeployerTradingFeeShare(v))
+                } else if let Some(v) = enable_freeze_privilege {
+                    Ok(SpotDeploy::EnableFreezePrivilege(v))
+                } else {
+                    Err(de::Error::missing_field(
+                        "one of the spotDeploy action fields",
+                    ))
+                }
+            }
+        }
+
+        deserializer.deserialize_map(SpotDeployVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::exchange::{ActionKind, action_kind::l1_action_hash};
+
+    #[test]
+    fn test_spot_deploy_round_trip() -> serde_json::Result<()> {
+        let action =
+            ActionKind::SpotDeploy(SpotDeploy::RegisterSpot(RegisterSpot { tokens: (1205, 0) }));
+
+        let json = serde_json::to_string(&action)?;
+        assert_eq!(
+            json,
+            r#"{"type":"spotDeploy","registerSpot":{"tokens":[1205,0]}}"#
+        );
+
+        let ActionKind::SpotDeploy(SpotDeploy::RegisterSpot(register_spot)) =
+            serde_json::from_str(&json)?
+        else {
+            panic!("expected a registerSpot action");
+        };
+        assert_eq!(register_spot.tokens, (1205, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_token_without_full_name() -> crate::Result<()> {
+        let action = ActionKind::SpotDeploy(SpotDeploy::RegisterToken2(RegisterToken2 {
+            spec: TokenSpec {
+                name: "TEST".to_string(),
+                sz_decimals: 2,
+                wei_decimals: 8,
+            },
+            max_gas: 1_000_000,
+            full_name: None,
+        }));
+
+        // The SDKs leave the field out rather than sending null, and the
+        // hash covers the exact fields sent.
+        let wire = json!({
+            "type": "spotDeploy",
+            "registerToken2": {
+                "spec": { "name": "TEST", "szDecimals": 2, "weiDecimals": 8 },
+                "maxGas": 1_000_000,
+            },
+        });
+        assert_eq!(serde_json::to_value(&action).unwrap(), wire);
+        assert_eq!(
+            action.hash(1_700_000_000_000, None)?,
+            l1_action_hash(&wire, 1_700_000_000_000, None)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::serialize_address;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubAccount {
+    pub name: String,
+}
+
+/// Moves USDC between the master account and one of its sub-accounts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    #[serde(serialize_with = "serialize_address")]
+    pub sub_account_user: Address,
+    pub is_deposit: bool,
+    pub usd: u64,
+}
+
+/// Moves a spot token between the master account and one of its sub-accounts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountSpotTransfer {
+    #[serde(serialize_with = "serialize_address")]
+    pub sub_account_user: Address,
+    pub is_deposit: bool,
+    pub token: String,
+    pub amount: String,
+}
@@ -0,0 +1,33 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::serialize_address;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVault {
+    pub name: String,
+    pub description: String,
+    /// Initial deposit of the leader, in USDC micro units.
+    pub initial_usd: u64,
+    pub nonce: u64,
+}
+
+/// Leaves a setting unchanged when its field is `None`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    #[serde(serialize_with = "serialize_address")]
+    pub vault_address: Address,
+    pub allow_deposits: Option<bool>,
+    pub always_close_on_withdraw: Option<bool>,
+}
+
+/// Distributes `usd` (in USDC micro units) from the vault to its followers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    #[serde(serialize_with = "serialize_address")]
+    pub vault_address: Address,
+    pub usd: u64,
+}
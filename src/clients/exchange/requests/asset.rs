@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use crate::{Error, Result};
+
+/// Looks up the asset id of `coin`.
+///
+/// Assets of builder-deployed perp dexs are named "dex:COIN". Dex names are
+/// lowercase, so "DEX:COIN" resolves to the same asset.
+pub fn resolve_asset(coin_to_asset: &HashMap<String, u32>, coin: &str) -> Result<u32> {
+    if let Some(&asset) = coin_to_asset.get(coin) {
+        return Ok(asset);
+    }
+
+    match coin.split_once(':') {
+        Some((dex, name)) => coin_to_asset
+            .get(&format!("{}:{name}", dex.to_lowercase()))
+            .copied()
+            .ok_or(Error::AssetNotFound),
+        None => Err(Error::AssetNotFound),
+    }
+}
@@ -1,10 +1,42 @@
+use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::float_to_string_for_hashing;
+
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BuilderInfo {
     #[serde(rename = "b")]
     pub builder: String,
+    /// Fee in tenths of a basis point.
     #[serde(rename = "f")]
     pub fee: u64,
 }
+
+impl BuilderInfo {
+    pub fn new(builder: Address, fee: u64) -> Self {
+        Self {
+            builder: builder.to_string().to_lowercase(),
+            fee,
+        }
+    }
+
+    /// `fee` as the percentage string `ApproveBuilderFee` expects, e.g.
+    /// "0.001%" for a fee of 1.
+    pub fn max_fee_rate(&self) -> String {
+        format!("{}%", float_to_string_for_hashing(self.fee as f64 / 1000.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_fee_rate() {
+        let builder = BuilderInfo::new(Address::repeat_byte(0xAB), 10);
+        assert_eq!(builder.builder, format!("0x{}", "ab".repeat(20)));
+        assert_eq!(builder.max_fee_rate(), "0.01%");
+        assert_eq!(BuilderInfo::new(Address::ZERO, 1).max_fee_rate(), "0.001%");
+    }
+}
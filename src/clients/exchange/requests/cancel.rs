@@ -1,12 +1,25 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{Result, exchange::requests::resolve_asset, utils::uuid_to_hex_string};
+
 #[derive(Debug)]
 pub struct ClientCancelRequest {
     pub asset: String,
     pub oid: u64,
 }
 
+impl ClientCancelRequest {
+    pub fn convert(self, coin_to_asset: &HashMap<String, u32>) -> Result<CancelRequest> {
+        Ok(CancelRequest {
+            asset: resolve_asset(coin_to_asset, &self.asset)?,
+            oid: self.oid,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CancelRequest {
     #[serde(rename = "a", alias = "asset")]
@@ -21,6 +34,15 @@ pub struct ClientCancelRequestCloid {
     pub cloid: Uuid,
 }
 
+impl ClientCancelRequestCloid {
+    pub fn convert(self, coin_to_asset: &HashMap<String, u32>) -> Result<CancelRequestCloid> {
+        Ok(CancelRequestCloid {
+            asset: resolve_asset(coin_to_asset, &self.asset)?,
+            cloid: uuid_to_hex_string(self.cloid),
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CancelRequestCloid {
     pub asset: u32,
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use crate::{
+    Result,
+    exchange::requests::{UpdateIsolatedMargin, UpdateLeverage, resolve_asset},
+};
+
+#[derive(Debug)]
+pub struct ClientUpdateLeverage {
+    pub asset: String,
+    pub is_cross: bool,
+    pub leverage: u32,
+}
+
+impl ClientUpdateLeverage {
+    pub fn convert(self, coin_to_asset: &HashMap<String, u32>) -> Result<UpdateLeverage> {
+        Ok(UpdateLeverage {
+            asset: resolve_asset(coin_to_asset, &self.asset)?,
+            is_cross: self.is_cross,
+            leverage: self.leverage,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ClientUpdateIsolatedMargin {
+    pub asset: String,
+    pub is_buy: bool,
+    /// Margin to add (or remove, when negative) in USDC micro units.
+    pub ntli: i64,
+}
+
+impl ClientUpdateIsolatedMargin {
+    pub fn convert(self, coin_to_asset: &HashMap<String, u32>) -> Result<UpdateIsolatedMargin> {
+        Ok(UpdateIsolatedMargin {
+            asset: resolve_asset(coin_to_asset, &self.asset)?,
+            is_buy: self.is_buy,
+            ntli: self.ntli,
+        })
+    }
+}
@@ -1,11 +1,17 @@
 mod actions;
+mod asset;
 mod builder_info;
 mod cancel;
+mod leverage;
 mod modify;
 mod orders;
+mod twap;
 
 pub use actions::*;
+pub use asset::*;
 pub use builder_info::*;
 pub use cancel::*;
+pub use leverage::*;
 pub use modify::*;
 pub use orders::*;
+pub use twap::*;
@@ -5,7 +5,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    Error, Result,
+    Result,
+    exchange::requests::resolve_asset,
     utils::{float_to_string_for_hashing, uuid_to_hex_string},
 };
 
@@ -108,7 +109,7 @@ impl ClientOrderRequest {
                 tpsl: trigger.tpsl,
             }),
         };
-        let &asset = coin_to_asset.get(&self.asset).ok_or(Error::AssetNotFound)?;
+        let asset = resolve_asset(coin_to_asset, &self.asset)?;
 
         let cloid = self.cloid.map(uuid_to_hex_string);
 
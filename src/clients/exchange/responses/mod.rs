@@ -1,11 +1,12 @@
-use serde::Deserialize;
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RestingOrder {
     pub oid: u64,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FilledOrder {
     pub total_sz: String,
@@ -13,7 +14,7 @@ pub struct FilledOrder {
     pub oid: u64,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum ExchangeDataStatus {
     Success,
@@ -24,22 +25,98 @@ pub enum ExchangeDataStatus {
     Filled(FilledOrder),
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ExchangeDataStatuses {
     pub statuses: Vec<ExchangeDataStatus>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapRunning {
+    pub twap_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapStatus {
+    Running(TwapRunning),
+    Success,
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapDataStatus {
+    pub status: TwapStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ExchangeResponseData {
+    Statuses(ExchangeDataStatuses),
+    Twap(TwapDataStatus),
+    /// Address of a newly created sub-account or vault.
+    Address(Address),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ExchangeResponse {
     #[serde(rename = "type")]
     pub response_type: String,
-    pub data: Option<ExchangeDataStatuses>,
+    pub data: Option<ExchangeResponseData>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "status", content = "response")]
 pub enum ExchangeResponseStatus {
     Ok(ExchangeResponse),
     Err(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_twap_responses() -> serde_json::Result<()> {
+        let order: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"running":{"twapId":77738308}}}}}"#,
+        )?;
+        let ExchangeResponseStatus::Ok(ExchangeResponse {
+            data:
+                Some(ExchangeResponseData::Twap(TwapDataStatus {
+                    status: TwapStatus::Running(running),
+                })),
+            ..
+        }) = order
+        else {
+            panic!("unexpected twapOrder response: {order:?}");
+        };
+        assert_eq!(running.twap_id, 77738308);
+
+        let cancel: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":"success"}}}"#,
+        )?;
+        assert!(matches!(
+            cancel,
+            ExchangeResponseStatus::Ok(ExchangeResponse {
+                data: Some(ExchangeResponseData::Twap(TwapDataStatus {
+                    status: TwapStatus::Success,
+                })),
+                ..
+            })
+        ));
+
+        let order: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":77738308}}]}}}"#,
+        )?;
+        assert!(matches!(
+            order,
+            ExchangeResponseStatus::Ok(ExchangeResponse {
+                data: Some(ExchangeResponseData::Statuses(_)),
+                ..
+            })
+        ));
+        Ok(())
+    }
+}
@@ -1,10 +1,259 @@
+mod requests;
 mod types;
 
-pub struct HyperliquidInfoClient;
+use std::{collections::HashMap, sync::Arc};
+
+use alloy::primitives::Address;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    BaseUrl, Error, Result,
+    http::{Cassette, HttpClient},
+    utils::{perp_asset_id, spot_asset_id},
+};
+
+pub use requests::InfoRequest;
+pub use types::*;
+
+#[derive(Debug, Clone)]
+pub struct HyperliquidInfoClient {
+    http_client: HttpClient,
+}
 
 impl HyperliquidInfoClient {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(base_url: Option<BaseUrl>) -> Result<Self> {
+        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
+
+        Ok(Self {
+            http_client: HttpClient {
+                client: reqwest::Client::default(),
+                base_url: base_url.get_url(),
+                cassette: None,
+                dry_run: None,
+            },
+        })
+    }
+
+    /// Record requests to, or replay them from, `cassette`.
+    pub fn set_cassette(&mut self, cassette: Option<Arc<Cassette>>) {
+        self.http_client.cassette = cassette;
+    }
+
+    /// Send requests to `url`, such as a local mock, instead of the API of
+    /// the network the client was created for.
+    pub fn set_base_url(&mut self, url: String) {
+        self.http_client.base_url = url;
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn http_client_mut(&mut self) -> &mut HttpClient {
+        &mut self.http_client
+    }
+
+    /// Send any info request and deserialize the response into `T`.
+    pub async fn send_info_request<T: DeserializeOwned>(
+        &self,
+        info_request: &InfoRequest,
+    ) -> Result<T> {
+        let data =
+            serde_json::to_string(info_request).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        let output = self.http_client.post("/info", data).await?;
+        serde_json::from_str(&output).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    /// Perp positions and margin summary of `user`.
+    pub async fn user_state(&self, user: Address) -> Result<UserStateResponse> {
+        self.send_info_request(&InfoRequest::ClearinghouseState { user })
+            .await
+    }
+
+    pub async fn open_orders(&self, user: Address) -> Result<Vec<OpenOrdersResponse>> {
+        self.send_info_request(&InfoRequest::OpenOrders { user })
+            .await
+    }
+
+    /// Most recent fills of `user`, newest first.
+    pub async fn user_fills(&self, user: Address) -> Result<Vec<UserFillsResponse>> {
+        self.send_info_request(&InfoRequest::UserFills { user })
+            .await
+    }
+
+    /// Order book of `coin`: bids then asks, best price first.
+    pub async fn l2_snapshot(&self, coin: String) -> Result<L2SnapshotResponse> {
+        self.send_info_request(&InfoRequest::L2Book { coin }).await
+    }
+
+    pub async fn user_twap_slice_fills(&self, user: Address) -> Result<Vec<UserTwapSliceFill>> {
+        self.send_info_request(&InfoRequest::UserTwapSliceFills { user })
+            .await
+    }
+
+    pub async fn twap_history(&self, user: Address) -> Result<Vec<TwapHistoryResponse>> {
+        self.send_info_request(&InfoRequest::TwapHistory { user })
+            .await
+    }
+
+    /// Sub-accounts of `user` with their perp and spot states.
+    pub async fn sub_accounts(&self, user: Address) -> Result<Vec<SubAccountResponse>> {
+        let sub_accounts: Option<Vec<SubAccountResponse>> = self
+            .send_info_request(&InfoRequest::SubAccounts { user })
+            .await?;
+        Ok(sub_accounts.unwrap_or_default())
+    }
+
+    /// Details of a vault. Passing `user` fills in their follower state.
+    pub async fn vault_details(
+        &self,
+        vault_address: Address,
+        user: Option<Address>,
+    ) -> Result<VaultDetailsResponse> {
+        self.send_info_request(&InfoRequest::VaultDetails {
+            vault_address,
+            user,
+        })
+        .await
+    }
+
+    pub async fn user_vault_equities(&self, user: Address) -> Result<Vec<UserVaultEquity>> {
+        self.send_info_request(&InfoRequest::UserVaultEquities { user })
+            .await
+    }
+
+    pub async fn delegator_summary(&self, user: Address) -> Result<DelegatorSummaryResponse> {
+        self.send_info_request(&InfoRequest::DelegatorSummary { user })
+            .await
+    }
+
+    pub async fn delegations(&self, user: Address) -> Result<Vec<Delegation>> {
+        self.send_info_request(&InfoRequest::Delegations { user })
+            .await
+    }
+
+    pub async fn delegator_history(&self, user: Address) -> Result<Vec<DelegatorHistoryEvent>> {
+        self.send_info_request(&InfoRequest::DelegatorHistory { user })
+            .await
+    }
+
+    pub async fn delegator_rewards(&self, user: Address) -> Result<Vec<DelegatorReward>> {
+        self.send_info_request(&InfoRequest::DelegatorRewards { user })
+            .await
+    }
+
+    /// Spot tokens being deployed by `user` and the current token gas auction.
+    pub async fn spot_deploy_state(&self, user: Address) -> Result<SpotDeployStateResponse> {
+        self.send_info_request(&InfoRequest::SpotDeployState { user })
+            .await
+    }
+
+    pub async fn spot_pair_deploy_auction_status(&self) -> Result<GasAuction> {
+        self.send_info_request(&InfoRequest::SpotPairDeployAuctionStatus)
+            .await
+    }
+
+    /// Perp universe and margin tables, of the given builder-deployed dex or
+    /// of the main dex when `dex` is `None`.
+    pub async fn meta(&self, dex: Option<String>) -> Result<MetaResponse> {
+        self.send_info_request(&InfoRequest::Meta { dex }).await
+    }
+
+    /// All perp dexs, in dex index order. The main dex comes first as `None`.
+    pub async fn perp_dexs(&self) -> Result<Vec<Option<PerpDex>>> {
+        self.send_info_request(&InfoRequest::PerpDexs).await
+    }
+
+    /// Spot pairs and tokens.
+    pub async fn spot_meta(&self) -> Result<SpotMetaResponse> {
+        self.send_info_request(&InfoRequest::SpotMeta).await
+    }
+
+    pub async fn perp_deploy_auction_status(&self) -> Result<GasAuction> {
+        self.send_info_request(&InfoRequest::PerpDeployAuctionStatus)
+            .await
+    }
+
+    /// Mid price of every coin, of the given builder-deployed dex or of the
+    /// main dex when `dex` is `None`.
+    pub async fn all_mids(&self, dex: Option<String>) -> Result<HashMap<String, String>> {
+        self.send_info_request(&InfoRequest::AllMids { dex }).await
+    }
+
+    /// Agents approved by `user`.
+    pub async fn extra_agents(&self, user: Address) -> Result<Vec<ExtraAgent>> {
+        self.send_info_request(&InfoRequest::ExtraAgents { user })
+            .await
+    }
+
+    /// Highest builder fee, in tenths of a basis point, `user` approved for
+    /// `builder`.
+    pub async fn max_builder_fee(&self, user: Address, builder: Address) -> Result<u64> {
+        self.send_info_request(&InfoRequest::MaxBuilderFee { user, builder })
+            .await
+    }
+
+    /// Asset ids of every perp, keyed as in
+    /// [`perp_coin_to_asset`](Self::perp_coin_to_asset), and of every spot
+    /// pair, keyed by pair name ("PURR/USDC" or "@107") and by "BASE/QUOTE"
+    /// token names. Pass the result to `ExchangeClient::new`.
+    pub async fn coin_to_asset(&self) -> Result<HashMap<String, u32>> {
+        let mut coin_to_asset = self.perp_coin_to_asset().await?;
+        insert_spot_assets(&mut coin_to_asset, &self.spot_meta().await?);
+        Ok(coin_to_asset)
+    }
+
+    /// Asset ids of every perp on every dex, keyed by coin ("BTC" on the main
+    /// dex, "dex:COIN" on builder-deployed dexs). Spot pairs are left out,
+    /// see [`coin_to_asset`](Self::coin_to_asset).
+    pub async fn perp_coin_to_asset(&self) -> Result<HashMap<String, u32>> {
+        let mut coin_to_asset = HashMap::new();
+
+        for (dex_index, dex) in self.perp_dexs().await?.into_iter().enumerate() {
+            let dex_name = dex.map(|dex| dex.name);
+            let meta = self.meta(dex_name.clone()).await?;
+            insert_perp_dex_assets(
+                &mut coin_to_asset,
+                dex_index as u32,
+                dex_name.as_deref(),
+                &meta,
+            );
+        }
+
+        Ok(coin_to_asset)
+    }
+}
+
+fn insert_spot_assets(coin_to_asset: &mut HashMap<String, u32>, spot_meta: &SpotMetaResponse) {
+    for pair in &spot_meta.universe {
+        let asset = spot_asset_id(pair.index);
+        coin_to_asset.insert(pair.name.clone(), asset);
+
+        let token_name = |index: u32| {
+            spot_meta
+                .tokens
+                .iter()
+                .find(|token| token.index == index)
+                .map(|token| token.name.as_str())
+        };
+        if let (Some(base), Some(quote)) = (token_name(pair.tokens.0), token_name(pair.tokens.1)) {
+            coin_to_asset
+                .entry(format!("{base}/{quote}"))
+                .or_insert(asset);
+        }
+    }
+}
+
+fn insert_perp_dex_assets(
+    coin_to_asset: &mut HashMap<String, u32>,
+    dex_index: u32,
+    dex_name: Option<&str>,
+    meta: &MetaResponse,
+) {
+    for (asset_index, asset) in meta.universe.iter().enumerate() {
+        let coin = match dex_name {
+            Some(dex) if !asset.name.contains(':') => format!("{dex}:{}", asset.name),
+            _ => asset.name.clone(),
+        };
+        coin_to_asset.insert(coin, perp_asset_id(dex_index, asset_index as u32));
     }
 }
 
@@ -12,12 +261,77 @@ impl HyperliquidInfoClient {
 mod tests {
     use super::*;
 
-    fn setup() -> HyperliquidInfoClient {
-        HyperliquidInfoClient::new()
+    fn setup() -> Result<HyperliquidInfoClient> {
+        HyperliquidInfoClient::new(Some(BaseUrl::Testnet))
     }
 
     #[test]
-    fn test_new() {
-        let client = setup();
+    fn test_new() -> Result<()> {
+        let client = setup()?;
+        assert!(!client.http_client.is_mainnet());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_perp_dex_assets() -> serde_json::Result<()> {
+        let main: MetaResponse = serde_json::from_str(
+            r#"{"universe":[{"name":"BTC","szDecimals":5,"maxLeverage":40},{"name":"ETH","szDecimals":4,"maxLeverage":25}]}"#,
+        )?;
+        let test: MetaResponse = serde_json::from_str(
+            r#"{"universe":[{"name":"test:ABC","szDecimals":2,"maxLeverage":10},{"name":"XYZ","szDecimals":2,"maxLeverage":10}]}"#,
+        )?;
+
+        let mut coin_to_asset = HashMap::new();
+        insert_perp_dex_assets(&mut coin_to_asset, 0, None, &main);
+        insert_perp_dex_assets(&mut coin_to_asset, 1, Some("test"), &test);
+
+        assert_eq!(coin_to_asset["BTC"], 0);
+        assert_eq!(coin_to_asset["ETH"], 1);
+        assert_eq!(coin_to_asset["test:ABC"], 110000);
+        assert_eq!(coin_to_asset["test:XYZ"], 110001);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_spot_assets() -> serde_json::Result<()> {
+        let spot_meta: SpotMetaResponse = serde_json::from_str(
+            r#"{
+                "universe": [
+                    {"name":"PURR/USDC","tokens":[1,0],"index":0,"isCanonical":true},
+                    {"name":"@107","tokens":[150,0],"index":107,"isCanonical":false}
+                ],
+                "tokens": [
+                    {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                    {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},
+                    {"name":"HYPE","szDecimals":2,"weiDecimals":8,"index":150,"tokenId":"0x0d01dc56dcaaca66ad901c959b4011ec","isCanonical":false}
+                ]
+            }"#,
+        )?;
+
+        let mut coin_to_asset = HashMap::from([("BTC".to_string(), 0)]);
+        insert_spot_assets(&mut coin_to_asset, &spot_meta);
+
+        assert_eq!(coin_to_asset["BTC"], 0);
+        assert_eq!(coin_to_asset["PURR/USDC"], 10000);
+        assert_eq!(coin_to_asset["@107"], 10107);
+        assert_eq!(coin_to_asset["HYPE/USDC"], 10107);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delegator_history_keeps_unknown_deltas() -> serde_json::Result<()> {
+        let history: Vec<DelegatorHistoryEvent> = serde_json::from_str(
+            r#"[
+                {"time":1,"hash":"0x01","delta":{"cDeposit":{"amount":"10.0"}}},
+                {"time":2,"hash":"0x02","delta":{"rewardClaim":{"amount":"0.5"}}}
+            ]"#,
+        )?;
+
+        assert!(matches!(history[0].delta, DelegatorDelta::CDeposit(_)));
+        let DelegatorDelta::Unknown(delta) = &history[1].delta else {
+            panic!("expected an unknown delta, got {:?}", history[1].delta);
+        };
+        assert_eq!(delta["rewardClaim"]["amount"], "0.5");
+        Ok(())
     }
 }
@@ -0,0 +1,71 @@
+use alloy::primitives::Address;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum InfoRequest {
+    UserTwapSliceFills {
+        user: Address,
+    },
+    TwapHistory {
+        user: Address,
+    },
+    SubAccounts {
+        user: Address,
+    },
+    VaultDetails {
+        vault_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<Address>,
+    },
+    UserVaultEquities {
+        user: Address,
+    },
+    DelegatorSummary {
+        user: Address,
+    },
+    Delegations {
+        user: Address,
+    },
+    DelegatorHistory {
+        user: Address,
+    },
+    DelegatorRewards {
+        user: Address,
+    },
+    SpotDeployState {
+        user: Address,
+    },
+    SpotPairDeployAuctionStatus,
+    Meta {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
+    PerpDexs,
+    SpotMeta,
+    PerpDeployAuctionStatus,
+    AllMids {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
+    ExtraAgents {
+        user: Address,
+    },
+    ClearinghouseState {
+        user: Address,
+    },
+    OpenOrders {
+        user: Address,
+    },
+    UserFills {
+        user: Address,
+    },
+    L2Book {
+        coin: String,
+    },
+    MaxBuilderFee {
+        user: Address,
+        builder: Address,
+    },
+}
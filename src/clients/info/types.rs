@@ -65,6 +65,16 @@ pub struct UserTokenBalance {
     pub entry_ntl: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountResponse {
+    pub name: String,
+    pub sub_account_user: Address,
+    pub master: Address,
+    pub clearinghouse_state: UserStateResponse,
+    pub spot_state: UserTokenBalanceResponse,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFeesResponse {
@@ -308,3 +318,280 @@ pub struct Leverage {
     pub value: u32,
     pub raw_usd: Option<String>,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapSliceFill {
+    pub fill: UserFillsResponse,
+    pub twap_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapHistoryResponse {
+    pub time: u64,
+    pub state: TwapState,
+    pub status: TwapHistoryStatus,
+    pub twap_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapState {
+    pub coin: String,
+    pub user: Address,
+    pub side: String,
+    pub sz: String,
+    pub executed_sz: String,
+    pub executed_ntl: String,
+    pub minutes: u64,
+    pub reduce_only: bool,
+    pub randomize: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwapHistoryStatus {
+    pub status: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetailsResponse {
+    pub name: String,
+    pub vault_address: Address,
+    pub leader: Address,
+    pub description: String,
+    /// PnL and account value history keyed by period ("day", "week", "allTime", ...).
+    pub portfolio: Vec<(String, VaultPortfolio)>,
+    pub apr: f64,
+    pub follower_state: Option<VaultFollower>,
+    pub leader_fraction: f64,
+    pub leader_commission: f64,
+    pub followers: Vec<VaultFollower>,
+    pub max_distributable: f64,
+    pub max_withdrawable: f64,
+    pub is_closed: bool,
+    pub allow_deposits: bool,
+    #[serde(default)]
+    pub always_close_on_withdraw: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPortfolio {
+    pub account_value_history: Vec<(u64, String)>,
+    pub pnl_history: Vec<(u64, String)>,
+    pub vlm: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    /// Follower address, or "Leader" for the vault leader.
+    pub user: String,
+    pub vault_equity: String,
+    pub pnl: String,
+    pub all_time_pnl: String,
+    pub days_following: u64,
+    pub vault_entry_time: u64,
+    pub lockup_until: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserVaultEquity {
+    pub vault_address: Address,
+    pub equity: String,
+    pub locked_until_timestamp: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorSummaryResponse {
+    pub delegated: String,
+    pub undelegated: String,
+    pub total_pending_withdrawal: String,
+    pub n_pending_withdrawals: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    pub validator: Address,
+    pub amount: String,
+    pub locked_until_timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DelegatorHistoryEvent {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    Delegate(DelegateDelta),
+    CDeposit(StakingAmountDelta),
+    Withdrawal(StakingWithdrawalDelta),
+    /// Delta of a kind not listed above, as sent, so that new kinds do not
+    /// break parsing the history.
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegateDelta {
+    pub validator: Address,
+    pub amount: String,
+    pub is_undelegate: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StakingAmountDelta {
+    pub amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StakingWithdrawalDelta {
+    pub amount: String,
+    pub phase: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorReward {
+    pub time: u64,
+    pub source: String,
+    pub total_amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotDeployStateResponse {
+    pub states: Vec<SpotDeployState>,
+    pub gas_auction: GasAuction,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotDeployState {
+    pub token: u32,
+    pub spec: SpotTokenSpec,
+    pub full_name: Option<String>,
+    pub spots: Vec<u32>,
+    pub hyperliquidity_genesis_balance: String,
+    pub total_genesis_balance_wei: String,
+    pub user_genesis_balances: Vec<(String, String)>,
+    pub existing_token_genesis_balances: Vec<(u32, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotTokenSpec {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+}
+
+/// Dutch auction for deployment gas; the price decays from `start_gas` to
+/// `end_gas` over `duration_seconds`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasAuction {
+    pub start_time_seconds: u64,
+    pub duration_seconds: u64,
+    pub start_gas: String,
+    pub current_gas: Option<String>,
+    pub end_gas: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaResponse {
+    pub universe: Vec<AssetMeta>,
+    #[serde(default)]
+    pub margin_tables: Vec<(u32, MarginTable)>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetMeta {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub max_leverage: u32,
+    pub margin_table_id: Option<u32>,
+    #[serde(default)]
+    pub only_isolated: bool,
+    #[serde(default)]
+    pub is_delisted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotMetaResponse {
+    pub universe: Vec<SpotPairMeta>,
+    pub tokens: Vec<SpotTokenMeta>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPairMeta {
+    /// "PURR/USDC" for canonical pairs, "@index" for the others.
+    pub name: String,
+    /// Indices of the base and quote tokens.
+    pub tokens: (u32, u32),
+    pub index: u32,
+    #[serde(default)]
+    pub is_canonical: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotTokenMeta {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+    pub index: u32,
+    pub token_id: String,
+    #[serde(default)]
+    pub is_canonical: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTable {
+    pub description: String,
+    pub margin_tiers: Vec<MarginTier>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTier {
+    pub lower_bound: String,
+    pub max_leverage: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDex {
+    pub name: String,
+    pub full_name: String,
+    pub deployer: Address,
+    pub oracle_updater: Option<Address>,
+    pub fee_recipient: Option<Address>,
+}
+
+/// Agent approved by a user through `ApproveAgent`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAgent {
+    pub name: String,
+    pub address: Address,
+    /// Expiry in milliseconds, if the agent was approved with one.
+    pub valid_until: Option<u64>,
+}
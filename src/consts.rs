@@ -9,3 +9,15 @@ pub const EPSILON: f64 = 1e-9;
 pub const WIRE_DECIMALS: u8 = 8;
 
 pub(crate) const INF_BPS: u16 = 10_001;
+
+/// Chain id the SDK signs user-signed actions with.
+pub const SIGNATURE_CHAIN_ID: u64 = 0x66eee;
+
+/// Assets of builder-deployed perp dexs are numbered
+/// `100000 + dex_index * 10000 + asset_index`.
+pub(crate) const PERP_DEX_ASSET_OFFSET: u32 = 100_000;
+
+pub(crate) const PERP_DEX_ASSET_STRIDE: u32 = 10_000;
+
+/// Spot pairs are numbered `10000 + index`.
+pub(crate) const SPOT_ASSET_OFFSET: u32 = 10_000;
@@ -0,0 +1,285 @@
+//! Dead man's switch on top of `ScheduleCancel`.
+//!
+//! [`DeadMansSwitch`] keeps a scheduled cancel-all `lead` ahead of now and
+//! renews it every `renew_interval`. If the process dies or loses its
+//! connection, renewals stop and the exchange cancels all open orders once
+//! the deadline passes.
+//!
+//! Every deadline that passes counts as a trigger, and the exchange accepts
+//! at most 10 triggers per UTC day. Once the limit is reached the switch
+//! stops scheduling until the next day and reports it as a failure.
+
+use std::{future::Future, time::Duration};
+
+use alloy::signers::local::PrivateKeySigner;
+use log::{info, warn};
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    Error, Result,
+    exchange::{
+        ActionKind, ExchangeClient, builder::BuildAction, requests::ScheduleCancel,
+        responses::ExchangeResponseStatus,
+    },
+    utils::now_timestamp_ms,
+};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// The exchange rejects deadlines less than 5 seconds ahead.
+const MIN_LEAD: Duration = Duration::from_secs(5);
+
+pub trait CancelScheduler {
+    /// Schedule a cancel-all at `time` in milliseconds, or clear the
+    /// schedule when `time` is `None`.
+    fn schedule_cancel(&self, time: Option<u64>) -> impl Future<Output = Result<()>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadMansSwitchConfig {
+    /// How far ahead of now the cancel-all is scheduled.
+    pub lead: Duration,
+    /// Time between two renewals, shorter than `lead`.
+    pub renew_interval: Duration,
+    /// Consecutive failed renewals before the failure callback fires.
+    pub max_failures: u32,
+    /// Triggers allowed per UTC day.
+    pub max_daily_triggers: u32,
+}
+
+impl Default for DeadMansSwitchConfig {
+    fn default() -> Self {
+        Self {
+            lead: Duration::from_secs(60),
+            renew_interval: Duration::from_secs(15),
+            max_failures: 3,
+            max_daily_triggers: 10,
+        }
+    }
+}
+
+pub struct DeadMansSwitch<T, F> {
+    scheduler: T,
+    config: DeadMansSwitchConfig,
+    on_failure: F,
+    /// Deadline of the last accepted schedule.
+    deadline: Option<u64>,
+    consecutive_failures: u32,
+    /// UTC day and number of deadlines that passed on it.
+    triggers: (u64, u32),
+}
+
+impl<T: CancelScheduler, F: FnMut(&Error)> DeadMansSwitch<T, F> {
+    /// `on_failure` is called once `max_failures` renewals in a row failed,
+    /// and again on every failure after that until a renewal succeeds.
+    pub fn new(scheduler: T, config: DeadMansSwitchConfig, on_failure: F) -> Result<Self> {
+        if config.lead < MIN_LEAD || config.renew_interval >= config.lead {
+            return Err(Error::ScheduleCancel(format!(
+                "lead {:?} must be at least {MIN_LEAD:?} and above the renew interval {:?}",
+                config.lead, config.renew_interval
+            )));
+        }
+
+        Ok(Self {
+            scheduler,
+            config,
+            on_failure,
+            deadline: None,
+            consecutive_failures: 0,
+            triggers: (0, 0),
+        })
+    }
+
+    /// Deadline of the last accepted schedule in milliseconds.
+    pub fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    /// Push the deadline to `now_ms + lead`, returning the new deadline.
+    pub async fn renew(&mut self, now_ms: u64) -> Result<u64> {
+        match self.try_renew(now_ms).await {
+            Ok(deadline) => {
+                self.consecutive_failures = 0;
+                Ok(deadline)
+            }
+            Err(err) => {
+                self.consecutive_failures += 1;
+                if self.consecutive_failures >= self.config.max_failures {
+                    (self.on_failure)(&err);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn try_renew(&mut self, now_ms: u64) -> Result<u64> {
+        if let Some(deadline) = self.deadline
+            && deadline <= now_ms
+        {
+            warn!("scheduled cancel at {deadline} was triggered");
+            self.deadline = None;
+            self.record_trigger(deadline);
+        }
+
+        let today = now_ms / DAY_MS;
+        if self.triggers.0 == today && self.triggers.1 >= self.config.max_daily_triggers {
+            return Err(Error::ScheduleCancel(format!(
+                "daily limit of {} triggers reached",
+                self.config.max_daily_triggers
+            )));
+        }
+
+        let deadline = now_ms + self.config.lead.as_millis() as u64;
+        self.scheduler.schedule_cancel(Some(deadline)).await?;
+        self.deadline = Some(deadline);
+        Ok(deadline)
+    }
+
+    fn record_trigger(&mut self, deadline: u64) {
+        let day = deadline / DAY_MS;
+        if self.triggers.0 == day {
+            self.triggers.1 += 1;
+        } else {
+            self.triggers = (day, 1);
+        }
+    }
+
+    /// Remove the scheduled cancel-all.
+    pub async fn clear(&mut self) -> Result<()> {
+        self.scheduler.schedule_cancel(None).await?;
+        self.deadline = None;
+        Ok(())
+    }
+
+    /// Renew every `renew_interval` until `shutdown` resolves, then clear
+    /// the schedule.
+    pub async fn run_until<S: Future<Output = ()>>(mut self, shutdown: S) {
+        let mut interval = tokio::time::interval(self.config.renew_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => match self.renew(now_timestamp_ms()).await {
+                    Ok(deadline) => info!("scheduled cancel renewed to {deadline}"),
+                    Err(err) => warn!("renewing scheduled cancel failed: {err}"),
+                },
+            }
+        }
+
+        match self.clear().await {
+            Ok(()) => info!("scheduled cancel cleared"),
+            Err(err) => warn!("clearing scheduled cancel failed: {err}"),
+        }
+    }
+}
+
+/// Schedules cancels through the exchange for the wallet's account, or its
+/// vault when the client has one.
+pub struct ExchangeCancelScheduler {
+    pub client: ExchangeClient,
+    pub wallet: PrivateKeySigner,
+}
+
+impl CancelScheduler for ExchangeCancelScheduler {
+    async fn schedule_cancel(&self, time: Option<u64>) -> Result<()> {
+        let status = ActionKind::ScheduleCancel(ScheduleCancel { time })
+            .build(&self.client)?
+            .sign(&self.wallet)?
+            .send()
+            .await?;
+
+        match status {
+            ExchangeResponseStatus::Ok(_) => Ok(()),
+            ExchangeResponseStatus::Err(err) => Err(Error::GenericRequest(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::test_utils::MockExchange;
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    #[tokio::test]
+    async fn test_renew_and_failure_callback() -> Result<()> {
+        let exchange = MockExchange::default();
+        let failures = Mutex::new(0);
+        let mut switch = DeadMansSwitch::new(&exchange, DeadMansSwitchConfig::default(), |_| {
+            *failures.lock().unwrap() += 1
+        })?;
+
+        assert_eq!(switch.renew(NOW).await?, NOW + 60_000);
+        assert_eq!(switch.renew(NOW + 15_000).await?, NOW + 75_000);
+
+        exchange.fail_next(4);
+        for i in 1..=4 {
+            assert!(switch.renew(NOW + 20_000 + i * 1_000).await.is_err());
+        }
+        assert_eq!(switch.deadline(), Some(NOW + 75_000));
+
+        switch.renew(NOW + 90_000).await?;
+        switch.clear().await?;
+
+        // The third and fourth failures in a row fire the callback.
+        assert_eq!(*failures.lock().unwrap(), 2);
+        assert_eq!(
+            exchange.requests(),
+            vec![
+                Some(NOW + 60_000),
+                Some(NOW + 75_000),
+                Some(NOW + 150_000),
+                None
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_daily_trigger_limit() -> Result<()> {
+        let exchange = MockExchange::default();
+        let config = DeadMansSwitchConfig {
+            max_daily_triggers: 2,
+            ..Default::default()
+        };
+        let mut switch = DeadMansSwitch::new(&exchange, config, |_| {})?;
+
+        // Each renewal after a passed deadline records a trigger.
+        let start = NOW - NOW % DAY_MS;
+        switch.renew(start).await?;
+        switch.renew(start + 120_000).await?;
+        assert!(switch.renew(start + 240_000).await.is_err());
+        assert!(switch.renew(start + 360_000).await.is_err());
+        assert_eq!(switch.deadline(), None);
+
+        // The limit resets on the next UTC day.
+        switch.renew(start + DAY_MS).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_until_clears_on_shutdown() -> Result<()> {
+        let exchange = MockExchange::default();
+        let switch = DeadMansSwitch::new(&exchange, DeadMansSwitchConfig::default(), |_| {})?;
+
+        switch.run_until(async {}).await;
+        assert_eq!(exchange.requests().last(), Some(&None));
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config = DeadMansSwitchConfig {
+            lead: Duration::from_secs(10),
+            renew_interval: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let exchange = MockExchange::default();
+        assert!(DeadMansSwitch::new(&exchange, config, |_| {}).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Dry-run mode for [`ExchangeClient`](crate::ExchangeClient).
+//!
+//! A client with a [`DryRun`] attached through `ExchangeClient::set_dry_run`
+//! builds, checks and signs actions as usual, but `SignedAction::send` never
+//! posts to `/exchange`. The body that would have been sent is kept in the
+//! dry run's log and a synthetic response is returned instead, chosen by the
+//! [`DryRunPolicy`]. `/info` requests are unaffected, so strategies can run
+//! against live mainnet data.
+//!
+//! ```no_run
+//! # fn example(mut client: hl_rs::ExchangeClient) {
+//! use hl_rs::dry_run::{DryRun, DryRunPolicy};
+//!
+//! let dry_run = DryRun::new(DryRunPolicy::ByTif);
+//! client.set_dry_run(Some(dry_run.clone()));
+//! // ... run the strategy with `client` ...
+//! for submission in dry_run.submissions() {
+//!     println!("{} {:?}", submission.payload, submission.response);
+//! }
+//! # }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use log::info;
+use serde_json::Value;
+
+use crate::{
+    Error, Result,
+    exchange::{
+        ActionKind,
+        requests::{Order, OrderRequest},
+        responses::{
+            ExchangeDataStatus, ExchangeDataStatuses, ExchangeResponse, ExchangeResponseData,
+            ExchangeResponseStatus, FilledOrder, RestingOrder, TwapDataStatus, TwapRunning,
+            TwapStatus,
+        },
+    },
+    utils::now_timestamp_ms,
+};
+
+/// First synthetic order and TWAP id, far from the ids the exchange hands
+/// out so they are not mistaken for real ones.
+const FIRST_OID: u64 = 1 << 52;
+
+/// How orders are answered. Other actions always succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DryRunPolicy {
+    /// Every order rests on the book.
+    Rest,
+    /// Every order fills in full at its limit price.
+    Fill,
+    /// `Ioc` orders fill at their limit price, other limit orders rest and
+    /// trigger orders wait for their trigger.
+    #[default]
+    ByTif,
+}
+
+/// Body that would have been posted to `/exchange`, with the response it
+/// was given.
+#[derive(Debug, Clone)]
+pub struct DryRunSubmission {
+    /// Time of the submission, in milliseconds.
+    pub time: u64,
+    pub payload: Value,
+    pub response: ExchangeResponseStatus,
+}
+
+#[derive(Debug)]
+struct DryRunState {
+    policy: DryRunPolicy,
+    submissions: Vec<DryRunSubmission>,
+    next_oid: u64,
+}
+
+/// Log of the actions sent through dry-run clients. Clones of a client share
+/// it.
+#[derive(Debug)]
+pub struct DryRun {
+    state: Mutex<DryRunState>,
+}
+
+impl DryRun {
+    pub fn new(policy: DryRunPolicy) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(DryRunState {
+                policy,
+                submissions: Vec::new(),
+                next_oid: FIRST_OID,
+            }),
+        })
+    }
+
+    pub fn policy(&self) -> DryRunPolicy {
+        self.state.lock().unwrap().policy
+    }
+
+    /// Change how later orders are answered.
+    pub fn set_policy(&self, policy: DryRunPolicy) {
+        self.state.lock().unwrap().policy = policy;
+    }
+
+    /// Every submission so far, oldest first.
+    pub fn submissions(&self) -> Vec<DryRunSubmission> {
+        self.state.lock().unwrap().submissions.clone()
+    }
+
+    /// Remove and return the submissions so far.
+    pub fn take_submissions(&self) -> Vec<DryRunSubmission> {
+        std::mem::take(&mut self.state.lock().unwrap().submissions)
+    }
+
+    /// Log an `/exchange` body and return the response to it.
+    pub(crate) fn submit(&self, data: &str) -> Result<String> {
+        let payload: Value =
+            serde_json::from_str(data).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let action_type = payload["action"]["type"].as_str().unwrap_or("unknown");
+        info!("dry run: not sending {action_type}");
+
+        let mut state = self.state.lock().unwrap();
+        // Custom actions that `ActionKind` does not know get the default
+        // response.
+        let response = match serde_json::from_value(payload["action"].clone()) {
+            Ok(action) => state.respond(&action),
+            Err(_) => response("default", None),
+        };
+        let response = ExchangeResponseStatus::Ok(response);
+        let body = serde_json::to_string(&response).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        state.submissions.push(DryRunSubmission {
+            time: now_timestamp_ms(),
+            payload,
+            response,
+        });
+        Ok(body)
+    }
+}
+
+impl DryRunState {
+    fn respond(&mut self, action: &ActionKind) -> ExchangeResponse {
+        match action {
+            ActionKind::Order(bulk_order) => {
+                let statuses = bulk_order
+                    .orders
+                    .iter()
+                    .map(|order| self.place(order))
+                    .collect();
+                statuses_response("order", statuses)
+            }
+            ActionKind::BatchModify(bulk_modify) => {
+                let statuses = bulk_modify
+                    .modifies
+                    .iter()
+                    .map(|modify| self.place(&modify.order))
+                    .collect();
+                statuses_response("order", statuses)
+            }
+            ActionKind::Cancel(bulk_cancel) => statuses_response(
+                "cancel",
+                vec![ExchangeDataStatus::Success; bulk_cancel.cancels.len()],
+            ),
+            ActionKind::CancelByCloid(bulk_cancel) => statuses_response(
+                "cancel",
+                vec![ExchangeDataStatus::Success; bulk_cancel.cancels.len()],
+            ),
+            ActionKind::TwapOrder(_) => {
+                let twap_id = self.next_oid();
+                twap_response("twapOrder", TwapStatus::Running(TwapRunning { twap_id }))
+            }
+            ActionKind::TwapCancel(_) => twap_response("twapCancel", TwapStatus::Success),
+            _ => response("default", None),
+        }
+    }
+
+    fn place(&mut self, order: &OrderRequest) -> ExchangeDataStatus {
+        let fills = match (&order.order_type, self.policy) {
+            (Order::Trigger(_), DryRunPolicy::ByTif) => {
+                return ExchangeDataStatus::WaitingForTrigger;
+            }
+            (Order::Limit(limit), DryRunPolicy::ByTif) => limit.tif == "Ioc",
+            (_, DryRunPolicy::Rest) => false,
+            (_, DryRunPolicy::Fill) => true,
+        };
+
+        let oid = self.next_oid();
+        if fills {
+            ExchangeDataStatus::Filled(FilledOrder {
+                total_sz: order.sz.clone(),
+                avg_px: order.limit_px.clone(),
+                oid,
+            })
+        } else {
+            ExchangeDataStatus::Resting(RestingOrder { oid })
+        }
+    }
+
+    fn next_oid(&mut self) -> u64 {
+        self.next_oid += 1;
+        self.next_oid - 1
+    }
+}
+
+fn response(response_type: &str, data: Option<ExchangeResponseData>) -> ExchangeResponse {
+    ExchangeResponse {
+        response_type: response_type.to_string(),
+        data,
+    }
+}
+
+fn statuses_response(response_type: &str, statuses: Vec<ExchangeDataStatus>) -> ExchangeResponse {
+    response(
+        response_type,
+        Some(ExchangeResponseData::Statuses(ExchangeDataStatuses {
+            statuses,
+        })),
+    )
+}
+
+fn twap_response(response_type: &str, status: TwapStatus) -> ExchangeResponse {
+    response(
+        response_type,
+        Some(ExchangeResponseData::Twap(TwapDataStatus { status })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::signers::local::PrivateKeySigner;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        BaseUrl, ExchangeClient,
+        exchange::builder::BuildAction,
+        test_utils::{action, bulk_order, limit_order},
+    };
+
+    fn order(tif: &str) -> Value {
+        limit_order(0, true, "100", "2", tif)
+    }
+
+    fn statuses(status: ExchangeResponseStatus) -> Vec<ExchangeDataStatus> {
+        match status {
+            ExchangeResponseStatus::Ok(ExchangeResponse {
+                data: Some(ExchangeResponseData::Statuses(statuses)),
+                ..
+            }) => statuses.statuses,
+            status => panic!("expected statuses, got {status:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_is_logged() -> Result<()> {
+        // Nothing listens on this address, so a real send would fail.
+        let mut client = ExchangeClient::new(Some(BaseUrl::Localhost), None, HashMap::new())?;
+        let dry_run = DryRun::new(DryRunPolicy::ByTif);
+        client.set_dry_run(Some(dry_run.clone()));
+        let wallet = PrivateKeySigner::random();
+
+        let signed = bulk_order(vec![order("Gtc"), order("Ioc")])
+            .build(&client)?
+            .sign(&wallet)?;
+        let payload = signed.to_payload()?;
+        let order_statuses = statuses(signed.send().await?);
+        assert!(matches!(
+            order_statuses[0],
+            ExchangeDataStatus::Resting(RestingOrder { oid: FIRST_OID })
+        ));
+        assert!(matches!(
+            &order_statuses[1],
+            ExchangeDataStatus::Filled(FilledOrder { total_sz, avg_px, oid })
+                if total_sz == "2" && avg_px == "100" && *oid == FIRST_OID + 1
+        ));
+
+        let cancel = action(json!({ "type": "cancel", "cancels": [{ "a": 0, "o": 1 }] }));
+        cancel.build(&client)?.sign(&wallet)?.send().await?;
+
+        let submissions = dry_run.take_submissions();
+        assert_eq!(submissions.len(), 2);
+        assert_eq!(submissions[0].payload.to_string(), payload);
+        assert!(matches!(
+            statuses(submissions[1].response.clone())[..],
+            [ExchangeDataStatus::Success]
+        ));
+        assert!(dry_run.submissions().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy() {
+        let dry_run = DryRun::new(DryRunPolicy::Rest);
+        let submit = |tif: &str| {
+            let body = json!({
+                "action": { "type": "order", "orders": [order(tif)], "grouping": "na" },
+                "nonce": 1,
+                "signature": null,
+                "vaultAddress": null,
+            });
+            let response = dry_run.submit(&body.to_string()).unwrap();
+            statuses(serde_json::from_str(&response).unwrap()).remove(0)
+        };
+
+        assert!(matches!(submit("Ioc"), ExchangeDataStatus::Resting(_)));
+        dry_run.set_policy(DryRunPolicy::Fill);
+        assert!(matches!(submit("Alo"), ExchangeDataStatus::Filled(_)));
+
+        let body = json!({ "action": { "type": "usdSend" }, "nonce": 1 });
+        let response: ExchangeResponseStatus =
+            serde_json::from_str(&dry_run.submit(&body.to_string()).unwrap()).unwrap();
+        assert!(matches!(
+            response,
+            ExchangeResponseStatus::Ok(ExchangeResponse { response_type, data: None })
+                if response_type == "default"
+        ));
+        assert_eq!(dry_run.submissions().len(), 3);
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::risk::RiskViolation;
+
 #[derive(Error, Debug, Clone)]
 pub enum Error {
     // TODO: turn some embedded types into errors instead of strings
@@ -63,4 +65,16 @@ pub enum Error {
     SignatureFailure(String),
     #[error("Vault address not found")]
     VaultAddressNotFound,
+    #[error("Invalid margin table: {0:?}")]
+    InvalidMarginTable(String),
+    #[error("Multi-sig error: {0:?}")]
+    MultiSig(String),
+    #[error("Schedule cancel error: {0:?}")]
+    ScheduleCancel(String),
+    #[error("Policy violation: {0:?}")]
+    PolicyViolation(String),
+    #[error("Remote signer error: {0:?}")]
+    RemoteSigner(String),
+    #[error("Risk check failed: {0}")]
+    RiskCheck(RiskViolation),
 }
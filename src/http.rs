@@ -1,7 +1,16 @@
+//! HTTP transport shared by the clients, with optional recording and
+//! replay of interactions through a [`Cassette`].
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
 use reqwest::{Client, Response};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{BaseUrl, Error, prelude::Result};
+use crate::{BaseUrl, Error, dry_run::DryRun, prelude::Result};
 
 #[derive(Deserialize, Debug)]
 struct ErrorData {
@@ -14,6 +23,144 @@ struct ErrorData {
 pub struct HttpClient {
     pub client: Client,
     pub base_url: String,
+    pub cassette: Option<Arc<Cassette>>,
+    /// Log `/exchange` requests here instead of sending them.
+    pub dry_run: Option<Arc<DryRun>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and append every interaction to the cassette file.
+    Record,
+    /// Serve responses from the cassette file without sending anything.
+    Replay,
+}
+
+/// Request/response pair kept in a cassette.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// `/info` or `/exchange`.
+    pub path: String,
+    pub request: Value,
+    pub status: u16,
+    pub response: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    /// Interactions already served in replay mode.
+    used: Vec<bool>,
+}
+
+/// Recorded `/info` and `/exchange` interactions, stored as JSON.
+///
+/// In replay mode a request is answered by the first unused interaction
+/// with the same path and body. Nonces and signatures change on every run,
+/// so `nonce` and `signature` fields, and the `time` of user-signed actions
+/// that carry their nonce in it, are ignored when matching.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Start recording into `path`, replacing its content.
+    pub fn record(path: impl AsRef<Path>) -> Arc<Self> {
+        Arc::new(Self {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Record,
+            state: Mutex::default(),
+        })
+    }
+
+    /// Replay the interactions recorded in `path`.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Arc<Self>> {
+        let data = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| Error::GenericReader(e.to_string()))?;
+        let file: CassetteFile =
+            serde_json::from_str(&data).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        Ok(Arc::new(Self {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Replay,
+            state: Mutex::new(CassetteState {
+                used: vec![false; file.interactions.len()],
+                interactions: file.interactions,
+            }),
+        }))
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    fn push(&self, interaction: Interaction) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.interactions.push(interaction);
+        state.used.push(false);
+
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        let data =
+            serde_json::to_string_pretty(&file).map_err(|e| Error::JsonParse(e.to_string()))?;
+        std::fs::write(&self.path, data).map_err(|e| Error::GenericReader(e.to_string()))
+    }
+
+    fn take(&self, path: &str, request: &Value) -> Result<Interaction> {
+        let key = match_key(request);
+        let mut state = self.state.lock().unwrap();
+
+        let index = state
+            .interactions
+            .iter()
+            .zip(&state.used)
+            .position(|(interaction, used)| {
+                !used && interaction.path == path && match_key(&interaction.request) == key
+            })
+            .ok_or_else(|| {
+                Error::GenericRequest(format!(
+                    "no recorded interaction in {} for {path} {request}",
+                    self.path.display()
+                ))
+            })?;
+        state.used[index] = true;
+        Ok(state.interactions[index].clone())
+    }
+}
+
+/// Request body with the fields that change on every run removed.
+fn match_key(request: &Value) -> Value {
+    let mut key = request.clone();
+    let Some(payload) = key.as_object_mut() else {
+        return key;
+    };
+
+    let nonce = payload.remove("nonce");
+    payload.remove("signature");
+    if let Some(action) = payload.get_mut("action").and_then(Value::as_object_mut) {
+        action.remove("nonce");
+        if nonce.is_some() && action.get("time") == nonce.as_ref() {
+            action.remove("time");
+        }
+    }
+    key
 }
 
 async fn parse_response(response: Response) -> Result<String> {
@@ -22,7 +169,10 @@ async fn parse_response(response: Response) -> Result<String> {
         .text()
         .await
         .map_err(|e| Error::GenericRequest(e.to_string()))?;
+    check_status(status_code, text)
+}
 
+fn check_status(status_code: u16, text: String) -> Result<String> {
     if status_code < 400 {
         return Ok(text);
     }
@@ -53,6 +203,49 @@ async fn parse_response(response: Response) -> Result<String> {
 
 impl HttpClient {
     pub async fn post(&self, url_path: &'static str, data: String) -> Result<String> {
+        if url_path == "/exchange"
+            && let Some(dry_run) = &self.dry_run
+        {
+            return dry_run.submit(&data);
+        }
+        let Some(cassette) = &self.cassette else {
+            return self.send(url_path, data).await;
+        };
+        let request = serde_json::from_str(&data).unwrap_or_else(|_| Value::String(data.clone()));
+
+        match cassette.mode {
+            CassetteMode::Replay => {
+                let interaction = cassette.take(url_path, &request)?;
+                check_status(interaction.status, interaction.response)
+            }
+            CassetteMode::Record => {
+                let full_url = format!("{}{url_path}", self.base_url);
+                let response = self
+                    .client
+                    .post(full_url)
+                    .header("Content-Type", "application/json")
+                    .body(data)
+                    .send()
+                    .await
+                    .map_err(|e| Error::GenericRequest(e.to_string()))?;
+                let status = response.status().as_u16();
+                let text = response
+                    .text()
+                    .await
+                    .map_err(|e| Error::GenericRequest(e.to_string()))?;
+
+                cassette.push(Interaction {
+                    path: url_path.to_string(),
+                    request,
+                    status,
+                    response: text.clone(),
+                })?;
+                check_status(status, text)
+            }
+        }
+    }
+
+    async fn send(&self, url_path: &'static str, data: String) -> Result<String> {
         let full_url = format!("{}{url_path}", self.base_url);
         let request = self
             .client
@@ -73,3 +266,66 @@ impl HttpClient {
         self.base_url == BaseUrl::Mainnet.get_url()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_match_key_ignores_nonce_and_signature() {
+        let request = |nonce: u64, r: &str| {
+            json!({
+                "action": { "type": "usdSend", "destination": "0x01", "amount": "1", "time": nonce },
+                "nonce": nonce,
+                "signature": { "r": r, "s": "0x2", "v": 27 },
+                "vaultAddress": null,
+            })
+        };
+        assert_eq!(match_key(&request(1, "0x1")), match_key(&request(2, "0x3")));
+
+        let schedule_cancel =
+            |time: u64| json!({ "action": { "type": "scheduleCancel", "time": time }, "nonce": 1 });
+        assert_ne!(
+            match_key(&schedule_cancel(5)),
+            match_key(&schedule_cancel(6))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "hl-rs-cassette-{}.json",
+            crate::utils::now_timestamp_ms()
+        ));
+        let interaction = |response: &str| Interaction {
+            path: "/info".to_string(),
+            request: json!({ "type": "allMids" }),
+            status: 200,
+            response: response.to_string(),
+        };
+        let file = CassetteFile {
+            interactions: vec![
+                interaction("{\"BTC\":\"1\"}"),
+                interaction("{\"BTC\":\"2\"}"),
+            ],
+        };
+        std::fs::write(&path, serde_json::to_string(&file).unwrap()).unwrap();
+
+        let client = HttpClient {
+            client: Client::default(),
+            base_url: "http://127.0.0.1:1".to_string(),
+            cassette: Some(Cassette::replay(&path)?),
+            dry_run: None,
+        };
+        std::fs::remove_file(path).ok();
+
+        let body = || "{\"type\":\"allMids\"}".to_string();
+        assert_eq!(client.post("/info", body()).await?, "{\"BTC\":\"1\"}");
+        assert_eq!(client.post("/info", body()).await?, "{\"BTC\":\"2\"}");
+        assert!(client.post("/info", body()).await.is_err());
+        assert!(client.post("/exchange", body()).await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Loading signing keys from hex strings, environment variables, encrypted
+//! JSON keystores and BIP-39 mnemonics.
+//!
+//! Passwords, phrases and decoded key bytes are zeroized once the signer is
+//! built, and the returned [`PrivateKeySigner`] zeroizes its key on drop.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use alloy::{
+    hex,
+    signers::local::{MnemonicBuilder, PrivateKeySigner, coins_bip39::English},
+};
+use zeroize::Zeroizing;
+
+use crate::{Error, Result};
+
+/// First account of the standard Ethereum derivation path.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Standard Ethereum derivation path of account `index`.
+pub fn derivation_path(index: u32) -> String {
+    format!("m/44'/60'/0'/0/{index}")
+}
+
+/// Where to read a keystore password from.
+#[derive(Debug, Clone)]
+pub enum Password {
+    /// Ask on the terminal without echoing.
+    Prompt,
+    /// First line of a file.
+    File(PathBuf),
+    /// Environment variable.
+    Env(String),
+}
+
+impl Password {
+    pub fn read(&self) -> Result<Zeroizing<String>> {
+        match self {
+            Password::Prompt => rpassword::prompt_password("Keystore password: ")
+                .map(Zeroizing::new)
+                .map_err(|e| Error::Wallet(e.to_string())),
+            Password::File(path) => {
+                let contents = Zeroizing::new(
+                    std::fs::read_to_string(path)
+                        .map_err(|e| Error::Wallet(format!("{}: {e}", path.display())))?,
+                );
+                Ok(Zeroizing::new(
+                    contents.lines().next().unwrap_or_default().to_string(),
+                ))
+            }
+            Password::Env(var) => read_env(var),
+        }
+    }
+}
+
+fn read_env(var: &str) -> Result<Zeroizing<String>> {
+    env::var(var)
+        .map(Zeroizing::new)
+        .map_err(|_| Error::Wallet(format!("{var} is not set")))
+}
+
+/// Signer from a hex private key, with or without `0x`.
+pub fn from_hex(key: &str) -> Result<PrivateKeySigner> {
+    let bytes =
+        Zeroizing::new(hex::decode(key.trim()).map_err(|e| Error::PrivateKeyParse(e.to_string()))?);
+    if bytes.len() != 32 {
+        return Err(Error::PrivateKeyParse(format!(
+            "expected 32 bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    PrivateKeySigner::from_slice(&bytes).map_err(|e| Error::PrivateKeyParse(e.to_string()))
+}
+
+/// Signer from a hex private key in the environment variable `var`.
+pub fn from_env(var: &str) -> Result<PrivateKeySigner> {
+    from_hex(&read_env(var)?)
+}
+
+/// Signer from an encrypted Ethereum JSON keystore, with either scrypt or
+/// pbkdf2 key derivation.
+pub fn from_keystore(path: impl AsRef<Path>, password: &Password) -> Result<PrivateKeySigner> {
+    let password = password.read()?;
+    PrivateKeySigner::decrypt_keystore(path, password.as_bytes())
+        .map_err(|e| Error::Wallet(e.to_string()))
+}
+
+/// Signer derived from an English BIP-39 `phrase` at `derivation_path`,
+/// such as [`DEFAULT_DERIVATION_PATH`]. `passphrase` is the optional
+/// BIP-39 passphrase, not a keystore password.
+pub fn from_mnemonic(
+    phrase: &str,
+    derivation_path: &str,
+    passphrase: Option<&str>,
+) -> Result<PrivateKeySigner> {
+    let phrase = Zeroizing::new(phrase.split_whitespace().collect::<Vec<_>>().join(" "));
+    let mut builder = MnemonicBuilder::<English>::default()
+        .phrase(phrase.as_str())
+        .derivation_path(derivation_path)
+        .map_err(|e| Error::Wallet(e.to_string()))?;
+    if let Some(passphrase) = passphrase {
+        builder = builder.password(passphrase);
+    }
+
+    builder.build().map_err(|e| Error::Wallet(e.to_string()))
+}
+
+/// Signer derived from the mnemonic in the environment variable `var`.
+pub fn mnemonic_from_env(var: &str, derivation_path: &str) -> Result<PrivateKeySigner> {
+    from_mnemonic(&read_env(var)?, derivation_path, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, address};
+
+    use super::*;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+    const ADDRESS: Address = address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_from_hex() -> Result<()> {
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        assert_eq!(from_hex(key)?.address(), ADDRESS);
+        assert_eq!(from_hex(&key[2..])?.address(), ADDRESS);
+        assert!(from_hex("0x1234").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_keystore() -> Result<()> {
+        let password =
+            std::env::temp_dir().join(format!("hl-rs-password-{}", crate::utils::next_nonce()));
+        std::fs::write(&password, "hl-rs\n").unwrap();
+        let password_file = Password::File(password.clone());
+
+        for kdf in ["pbkdf2", "scrypt"] {
+            let path = fixture(&format!("keystore-{kdf}.json"));
+            assert_eq!(from_keystore(&path, &password_file)?.address(), ADDRESS);
+        }
+
+        std::fs::write(&password, "wrong").unwrap();
+        assert!(from_keystore(fixture("keystore-pbkdf2.json"), &password_file).is_err());
+        std::fs::remove_file(password).ok();
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_mnemonic() -> Result<()> {
+        assert_eq!(
+            from_mnemonic(PHRASE, DEFAULT_DERIVATION_PATH, None)?.address(),
+            ADDRESS
+        );
+        assert_eq!(
+            from_mnemonic(&format!("  {PHRASE}\n"), &derivation_path(1), None)?.address(),
+            address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")
+        );
+        assert_ne!(
+            from_mnemonic(PHRASE, DEFAULT_DERIVATION_PATH, Some("extra"))?.address(),
+            ADDRESS
+        );
+        assert!(from_mnemonic("test junk", DEFAULT_DERIVATION_PATH, None).is_err());
+        Ok(())
+    }
+}
@@ -1,18 +1,32 @@
+pub mod agent;
+pub mod dead_mans_switch;
+pub mod dry_run;
+pub mod http;
+#[cfg(feature = "keys")]
+pub mod keys;
+pub mod margin;
+pub mod oracle;
+pub mod risk;
+pub mod signer;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
 
 mod clients;
 mod consts;
 mod eip712;
 mod error;
-mod http;
 mod prelude;
+#[cfg(test)]
+mod test_utils;
 mod types;
 
 pub use clients::{
     exchange::{self, ExchangeClient},
     info, ws,
 };
-pub use consts::{EPSILON, LOCAL_API_URL, MAINNET_API_URL, TESTNET_API_URL};
+pub use consts::{EPSILON, LOCAL_API_URL, MAINNET_API_URL, SIGNATURE_CHAIN_ID, TESTNET_API_URL};
+pub use eip712::Eip712;
 pub use error::Error;
 pub use prelude::Result;
 pub use types::BaseUrl;
@@ -0,0 +1,238 @@
+//! Margin table validation and margin math.
+//!
+//! A margin table is a list of tiers keyed by position notional. Each tier
+//! sets the max leverage `L` from its lower bound upward, and the maintenance
+//! margin rate of a tier is `1 / (2 * L)`. Maintenance margin is continuous
+//! across tiers: entering a tier deducts what the higher rate would otherwise
+//! charge on the notional below its lower bound.
+
+use crate::{
+    Error, Result,
+    exchange::requests::{RawMarginTable, RawMarginTier},
+    info::MarginTable,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum MarginMode {
+    /// `account_value` is the equity backing all cross positions.
+    Cross { account_value: f64 },
+    /// `margin` is the margin allocated to this position.
+    Isolated { margin: f64 },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tier {
+    lower_bound: f64,
+    max_leverage: u32,
+    maintenance_margin_rate: f64,
+    maintenance_deduction: f64,
+}
+
+/// Validated margin table.
+#[derive(Debug, Clone)]
+pub struct MarginSchedule {
+    tiers: Vec<Tier>,
+}
+
+impl MarginSchedule {
+    /// Validate `table`: the first tier starts at 0, lower bounds strictly
+    /// increase and max leverage is at least 1 and never increases.
+    pub fn new(table: &RawMarginTable) -> Result<Self> {
+        let tiers = &table.margin_tiers;
+        let first = tiers
+            .first()
+            .ok_or_else(|| Error::InvalidMarginTable("table has no tiers".to_string()))?;
+        if first.lower_bound != 0 {
+            return Err(Error::InvalidMarginTable(
+                "first tier must start at 0".to_string(),
+            ));
+        }
+
+        for (i, pair) in tiers.windows(2).enumerate() {
+            let (prev, tier) = (&pair[0], &pair[1]);
+            if tier.lower_bound <= prev.lower_bound {
+                return Err(Error::InvalidMarginTable(format!(
+                    "tier {} lower bound {} is not above {}",
+                    i + 1,
+                    tier.lower_bound,
+                    prev.lower_bound
+                )));
+            }
+            if tier.max_leverage > prev.max_leverage {
+                return Err(Error::InvalidMarginTable(format!(
+                    "tier {} max leverage {} is above {}",
+                    i + 1,
+                    tier.max_leverage,
+                    prev.max_leverage
+                )));
+            }
+        }
+        if tiers.iter().any(|tier| tier.max_leverage == 0) {
+            return Err(Error::InvalidMarginTable(
+                "max leverage must be at least 1".to_string(),
+            ));
+        }
+
+        let mut schedule: Vec<Tier> = Vec::with_capacity(tiers.len());
+        for tier in tiers {
+            let lower_bound = tier.lower_bound as f64;
+            let maintenance_margin_rate = 1.0 / (2.0 * tier.max_leverage as f64);
+            let maintenance_deduction = match schedule.last() {
+                Some(prev) => {
+                    prev.maintenance_deduction
+                        + lower_bound * (maintenance_margin_rate - prev.maintenance_margin_rate)
+                }
+                None => 0.0,
+            };
+            schedule.push(Tier {
+                lower_bound,
+                max_leverage: tier.max_leverage,
+                maintenance_margin_rate,
+                maintenance_deduction,
+            });
+        }
+
+        Ok(Self { tiers: schedule })
+    }
+
+    /// Validate a margin table as returned by the `meta` info query.
+    pub fn from_meta(table: &MarginTable) -> Result<Self> {
+        let margin_tiers = table
+            .margin_tiers
+            .iter()
+            .map(|tier| {
+                let lower_bound = tier
+                    .lower_bound
+                    .parse::<f64>()
+                    .map_err(|_| Error::FloatStringParse)?;
+                Ok(RawMarginTier {
+                    lower_bound: lower_bound as i64,
+                    max_leverage: tier.max_leverage,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Self::new(&RawMarginTable {
+            description: table.description.clone(),
+            margin_tiers,
+        })
+    }
+
+    fn tier(&self, notional: f64) -> &Tier {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| notional.abs() >= tier.lower_bound)
+            .unwrap_or(&self.tiers[0])
+    }
+
+    /// Max leverage allowed for a position of `notional` USD.
+    pub fn max_leverage(&self, notional: f64) -> u32 {
+        self.tier(notional).max_leverage
+    }
+
+    /// Maintenance margin required for a position of `notional` USD.
+    pub fn maintenance_margin(&self, notional: f64) -> f64 {
+        let tier = self.tier(notional);
+        notional.abs() * tier.maintenance_margin_rate - tier.maintenance_deduction
+    }
+
+    /// Estimated liquidation price of a position of signed `size` (negative
+    /// for shorts) entered at `entry_px`, or `None` if it cannot be
+    /// liquidated, e.g. a long backed by more than its notional.
+    ///
+    /// The maintenance margin rate is taken at the entry notional, so the
+    /// estimate drifts when the price move crosses a tier boundary.
+    pub fn liquidation_price(&self, size: f64, entry_px: f64, mode: MarginMode) -> Option<f64> {
+        if size == 0.0 {
+            return None;
+        }
+
+        let notional = size.abs() * entry_px;
+        let margin = match mode {
+            MarginMode::Cross { account_value } => account_value,
+            MarginMode::Isolated { margin } => margin,
+        };
+        let margin_available = margin - self.maintenance_margin(notional);
+        let rate = self.tier(notional).maintenance_margin_rate;
+        let side = size.signum();
+
+        let liquidation_px = entry_px - side * margin_available / size.abs() / (1.0 - rate * side);
+        (liquidation_px > 0.0).then_some(liquidation_px)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(tiers: &[(i64, u32)]) -> RawMarginTable {
+        RawMarginTable {
+            description: "test".to_string(),
+            margin_tiers: tiers
+                .iter()
+                .map(|&(lower_bound, max_leverage)| RawMarginTier {
+                    lower_bound,
+                    max_leverage,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_margin_table() {
+        assert!(MarginSchedule::new(&table(&[(0, 50), (1_000_000, 20)])).is_ok());
+        assert!(MarginSchedule::new(&table(&[])).is_err());
+        assert!(MarginSchedule::new(&table(&[(100, 50)])).is_err());
+        assert!(MarginSchedule::new(&table(&[(0, 20), (1_000_000, 50)])).is_err());
+        assert!(MarginSchedule::new(&table(&[(0, 50), (0, 20)])).is_err());
+        assert!(MarginSchedule::new(&table(&[(0, 0)])).is_err());
+    }
+
+    #[test]
+    fn test_maintenance_margin_is_continuous() -> Result<()> {
+        let schedule = MarginSchedule::new(&table(&[(0, 50), (1_000_000, 20)]))?;
+
+        assert_eq!(schedule.max_leverage(999_999.0), 50);
+        assert_eq!(schedule.max_leverage(1_000_000.0), 20);
+        assert!((schedule.maintenance_margin(500_000.0) - 5_000.0).abs() < 1e-6);
+        assert!((schedule.maintenance_margin(1_000_000.0) - 10_000.0).abs() < 1e-6);
+        assert!((schedule.maintenance_margin(2_000_000.0) - 35_000.0).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_liquidation_price() -> Result<()> {
+        let schedule = MarginSchedule::new(&table(&[(0, 10)]))?;
+
+        // 1 unit at 100 with 10 of isolated margin and 5 of maintenance margin.
+        let long = schedule
+            .liquidation_price(1.0, 100.0, MarginMode::Isolated { margin: 10.0 })
+            .unwrap();
+        assert!((long - (100.0 - 5.0 / 0.95)).abs() < 1e-9);
+
+        let short = schedule
+            .liquidation_price(
+                -1.0,
+                100.0,
+                MarginMode::Cross {
+                    account_value: 10.0,
+                },
+            )
+            .unwrap();
+        assert!((short - (100.0 + 5.0 / 1.05)).abs() < 1e-9);
+
+        assert!(
+            schedule
+                .liquidation_price(
+                    1.0,
+                    100.0,
+                    MarginMode::Cross {
+                        account_value: 200.0
+                    }
+                )
+                .is_none()
+        );
+        Ok(())
+    }
+}
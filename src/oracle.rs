@@ -0,0 +1,459 @@
+//! Oracle updater for perp deployers.
+//!
+//! Builder-deployed perp dexs need their oracle, mark and external perp
+//! prices pushed regularly through `PerpDeploy::SetOracle`. [`OracleUpdater`]
+//! pulls prices from a [`PriceSource`], drops moves outside the configured
+//! bands, batches the remaining prices into one `SetOracle` per dex and hands
+//! them to an [`OracleSubmitter`] on a fixed schedule. A move outside the band
+//! that holds for `reanchor_after` rounds is taken as real and submitted, so
+//! a coin is never stuck on a stale price.
+
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use alloy::signers::local::PrivateKeySigner;
+use log::{info, warn};
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    Error, Result,
+    exchange::{
+        ActionKind, ExchangeClient,
+        builder::BuildAction,
+        requests::{PerpDeploy, SetOracle},
+        responses::ExchangeResponseStatus,
+    },
+    info::HyperliquidInfoClient,
+    utils::float_to_string_for_hashing,
+};
+
+/// Prices of one coin on a builder-deployed dex.
+#[derive(Debug, Clone)]
+pub struct OraclePrice {
+    pub dex: String,
+    /// Coin as named on the dex, e.g. "test:ABC".
+    pub coin: String,
+    pub oracle_px: f64,
+    pub mark_px: Option<f64>,
+    pub external_perp_px: Option<f64>,
+}
+
+pub trait PriceSource {
+    fn fetch_prices(&self) -> impl Future<Output = Result<Vec<OraclePrice>>> + Send;
+}
+
+pub trait OracleSubmitter {
+    fn submit(&self, set_oracle: SetOracle) -> impl Future<Output = Result<()>> + Send;
+}
+
+#[derive(Debug, Clone)]
+pub struct OracleUpdaterConfig {
+    /// Time between two rounds of updates.
+    pub interval: Duration,
+    /// Largest accepted relative move from the last submitted price, e.g.
+    /// `0.1` for 10%.
+    pub max_change: f64,
+    /// Per-coin overrides of `max_change`.
+    pub max_change_overrides: HashMap<String, f64>,
+    /// Consecutive rounds a coin must stay outside its band, moving within
+    /// its band from one round to the next, before the new price is
+    /// accepted as reference.
+    pub reanchor_after: u32,
+}
+
+impl Default for OracleUpdaterConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(3),
+            max_change: 0.1,
+            max_change_overrides: HashMap::new(),
+            reanchor_after: 3,
+        }
+    }
+}
+
+/// Outcome of one round of updates.
+#[derive(Debug, Default)]
+pub struct OracleTick {
+    /// Dexs whose `SetOracle` was accepted.
+    pub submitted: Vec<String>,
+    /// Coins dropped because a price was invalid or outside its band.
+    pub rejected: Vec<String>,
+    /// Dexs whose `SetOracle` failed to go through.
+    pub failed: Vec<(String, Error)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastPrices {
+    oracle_px: f64,
+    mark_px: Option<f64>,
+}
+
+impl From<&OraclePrice> for LastPrices {
+    fn from(price: &OraclePrice) -> Self {
+        Self {
+            oracle_px: price.oracle_px,
+            mark_px: price.mark_px,
+        }
+    }
+}
+
+/// Prices of a coin seen outside its band in consecutive rounds.
+#[derive(Debug, Clone, Copy)]
+struct OutOfBand {
+    last: LastPrices,
+    rounds: u32,
+}
+
+pub struct OracleUpdater<S, T> {
+    source: S,
+    submitter: T,
+    config: OracleUpdaterConfig,
+    last_prices: HashMap<String, LastPrices>,
+    out_of_band: HashMap<String, OutOfBand>,
+}
+
+impl<S: PriceSource, T: OracleSubmitter> OracleUpdater<S, T> {
+    pub fn new(source: S, submitter: T, config: OracleUpdaterConfig) -> Self {
+        Self {
+            source,
+            submitter,
+            config,
+            last_prices: HashMap::new(),
+            out_of_band: HashMap::new(),
+        }
+    }
+
+    /// Fetch, validate and submit one round of prices.
+    ///
+    /// Only a failure of the price source is returned as an error; rejected
+    /// coins and failed submissions are reported in the [`OracleTick`], and
+    /// the band of a failed dex keeps its last accepted prices as reference.
+    /// Prices outside the band are rejected until they have held for
+    /// `reanchor_after` rounds.
+    pub async fn tick(&mut self) -> Result<OracleTick> {
+        let prices = self.source.fetch_prices().await?;
+        let mut tick = OracleTick::default();
+
+        let mut by_dex: HashMap<String, Vec<OraclePrice>> = HashMap::new();
+        for price in prices {
+            if self.accept(&price) {
+                by_dex.entry(price.dex.clone()).or_default().push(price);
+            } else {
+                warn!(
+                    "rejected oracle update for {}: oracle {} mark {:?}",
+                    price.coin, price.oracle_px, price.mark_px
+                );
+                tick.rejected.push(price.coin);
+            }
+        }
+
+        let mut dexs: Vec<_> = by_dex.into_iter().collect();
+        dexs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (dex, prices) in dexs {
+            match self.submitter.submit(set_oracle(&dex, &prices)).await {
+                Ok(()) => {
+                    for price in &prices {
+                        self.last_prices.insert(price.coin.clone(), price.into());
+                        self.out_of_band.remove(&price.coin);
+                    }
+                    tick.submitted.push(dex);
+                }
+                Err(err) => {
+                    warn!("oracle update for dex {dex} failed: {err}");
+                    tick.failed.push((dex, err));
+                }
+            }
+        }
+
+        Ok(tick)
+    }
+
+    /// Run [`tick`](Self::tick) every `interval` until `shutdown` resolves,
+    /// logging and riding out failures.
+    pub async fn run_until<F: Future<Output = ()>>(mut self, shutdown: F) {
+        let mut interval = tokio::time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return,
+                _ = interval.tick() => match self.tick().await {
+                    Ok(tick) => info!(
+                        "oracle tick: submitted {:?}, rejected {:?}, failed {}",
+                        tick.submitted,
+                        tick.rejected,
+                        tick.failed.len()
+                    ),
+                    Err(err) => warn!("fetching oracle prices failed: {err}"),
+                },
+            }
+        }
+    }
+
+    /// Whether `price` should be submitted: it is valid and within the band
+    /// around the last submitted prices, or it has been outside the band
+    /// for `reanchor_after` rounds without moving out of its own band.
+    fn accept(&mut self, price: &OraclePrice) -> bool {
+        let pxs = [Some(price.oracle_px), price.mark_px, price.external_perp_px];
+        if pxs
+            .into_iter()
+            .flatten()
+            .any(|px| !px.is_finite() || px <= 0.0)
+        {
+            return false;
+        }
+
+        let Some(last) = self.last_prices.get(&price.coin) else {
+            return true;
+        };
+        let max_change = self
+            .config
+            .max_change_overrides
+            .get(&price.coin)
+            .copied()
+            .unwrap_or(self.config.max_change);
+        if is_within(last, price, max_change) {
+            self.out_of_band.remove(&price.coin);
+            return true;
+        }
+
+        let out_of_band = self
+            .out_of_band
+            .entry(price.coin.clone())
+            .or_insert(OutOfBand {
+                last: price.into(),
+                rounds: 0,
+            });
+        if is_within(&out_of_band.last, price, max_change) {
+            out_of_band.rounds += 1;
+        } else {
+            out_of_band.rounds = 1;
+        }
+        out_of_band.last = price.into();
+
+        let reanchor = out_of_band.rounds >= self.config.reanchor_after;
+        if reanchor {
+            warn!(
+                "{} stayed outside its band for {} rounds, re-anchoring at {}",
+                price.coin, out_of_band.rounds, price.oracle_px
+            );
+        }
+        reanchor
+    }
+}
+
+fn is_within(last: &LastPrices, price: &OraclePrice, max_change: f64) -> bool {
+    let within = |last: f64, px: f64| ((px - last) / last).abs() <= max_change;
+
+    within(last.oracle_px, price.oracle_px)
+        && match (last.mark_px, price.mark_px) {
+            (Some(last), Some(px)) => within(last, px),
+            _ => true,
+        }
+}
+
+fn set_oracle(dex: &str, prices: &[OraclePrice]) -> SetOracle {
+    let mut prices: Vec<_> = prices.iter().collect();
+    prices.sort_by(|a, b| a.coin.cmp(&b.coin));
+
+    let wire = float_to_string_for_hashing;
+    let mark_pxs: Vec<_> = prices
+        .iter()
+        .filter_map(|price| Some((price.coin.clone(), wire(price.mark_px?))))
+        .collect();
+
+    SetOracle {
+        dex: dex.to_string(),
+        oracle_pxs: prices
+            .iter()
+            .map(|price| (price.coin.clone(), wire(price.oracle_px)))
+            .collect(),
+        mark_pxs: if mark_pxs.is_empty() {
+            vec![]
+        } else {
+            vec![mark_pxs]
+        },
+        external_perp_pxs: prices
+            .iter()
+            .filter_map(|price| Some((price.coin.clone(), wire(price.external_perp_px?))))
+            .collect(),
+    }
+}
+
+/// Submits `SetOracle` actions signed by the dex deployer or oracle updater.
+pub struct ExchangeOracleSubmitter {
+    pub client: ExchangeClient,
+    pub wallet: PrivateKeySigner,
+}
+
+impl OracleSubmitter for ExchangeOracleSubmitter {
+    async fn submit(&self, set_oracle: SetOracle) -> Result<()> {
+        let status = ActionKind::PerpDeploy(PerpDeploy::SetOracle(set_oracle))
+            .build(&self.client)?
+            .sign(&self.wallet)?
+            .send()
+            .await?;
+
+        match status {
+            ExchangeResponseStatus::Ok(_) => Ok(()),
+            ExchangeResponseStatus::Err(err) => Err(Error::GenericRequest(err)),
+        }
+    }
+}
+
+/// Mirrors mid prices of reference coins, by default of the main dex, onto
+/// the coins of a builder-deployed dex.
+pub struct MidsPriceSource {
+    pub info_client: HyperliquidInfoClient,
+    pub dex: String,
+    /// Dex where the reference coins trade, `None` for the main dex.
+    pub reference_dex: Option<String>,
+    /// Coin on the deployed dex and the reference coin it tracks.
+    pub coins: Vec<(String, String)>,
+}
+
+impl PriceSource for MidsPriceSource {
+    async fn fetch_prices(&self) -> Result<Vec<OraclePrice>> {
+        let mids = self
+            .info_client
+            .all_mids(self.reference_dex.clone())
+            .await?;
+
+        self.coins
+            .iter()
+            .map(|(coin, reference)| {
+                let px = mids
+                    .get(reference)
+                    .ok_or(Error::AssetNotFound)?
+                    .parse::<f64>()
+                    .map_err(|_| Error::FloatStringParse)?;
+                Ok(OraclePrice {
+                    dex: self.dex.clone(),
+                    coin: coin.clone(),
+                    oracle_px: px,
+                    mark_px: Some(px),
+                    external_perp_px: Some(px),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::test_utils::MockExchange;
+
+    struct FakePriceSource {
+        rounds: Mutex<Vec<Result<Vec<OraclePrice>>>>,
+    }
+
+    impl PriceSource for FakePriceSource {
+        async fn fetch_prices(&self) -> Result<Vec<OraclePrice>> {
+            self.rounds.lock().unwrap().remove(0)
+        }
+    }
+
+    fn price(dex: &str, coin: &str, px: f64) -> OraclePrice {
+        OraclePrice {
+            dex: dex.to_string(),
+            coin: coin.to_string(),
+            oracle_px: px,
+            mark_px: Some(px),
+            external_perp_px: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tick_batches_per_dex_and_enforces_bands() -> Result<()> {
+        let source = FakePriceSource {
+            rounds: Mutex::new(vec![
+                Ok(vec![
+                    price("b", "b:XYZ", 2.0),
+                    price("a", "a:DEF", 10.0),
+                    price("a", "a:ABC", 100.0),
+                ]),
+                Ok(vec![price("a", "a:ABC", 150.0), price("a", "a:DEF", 10.5)]),
+            ]),
+        };
+        let exchange = MockExchange::default();
+        let mut updater = OracleUpdater::new(source, &exchange, OracleUpdaterConfig::default());
+
+        let tick = updater.tick().await?;
+        assert_eq!(tick.submitted, vec!["a", "b"]);
+        {
+            let submitted = exchange.requests();
+            assert_eq!(submitted.len(), 2);
+            assert_eq!(
+                submitted[0].oracle_pxs,
+                vec![
+                    ("a:ABC".to_string(), "100".to_string()),
+                    ("a:DEF".to_string(), "10".to_string()),
+                ]
+            );
+            assert_eq!(submitted[0].mark_pxs.len(), 1);
+            assert!(submitted[0].external_perp_pxs.is_empty());
+        }
+
+        let tick = updater.tick().await?;
+        assert_eq!(tick.rejected, vec!["a:ABC"]);
+        assert_eq!(
+            exchange.requests()[2].oracle_pxs,
+            vec![("a:DEF".to_string(), "10.5".to_string())]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tick_reanchors_after_lasting_move() -> Result<()> {
+        let rounds = [100.0, 115.0, 300.0, 115.0, 115.0, 116.0, 117.0]
+            .into_iter()
+            .map(|px| Ok(vec![price("a", "a:ABC", px)]))
+            .collect();
+        let source = FakePriceSource {
+            rounds: Mutex::new(rounds),
+        };
+        let exchange = MockExchange::default();
+        let mut updater = OracleUpdater::new(source, &exchange, OracleUpdaterConfig::default());
+
+        updater.tick().await?;
+        // The spike to 300 restarts the count of rounds outside the band.
+        for _ in 0..4 {
+            assert_eq!(updater.tick().await?.rejected, vec!["a:ABC"]);
+        }
+        assert_eq!(updater.tick().await?.submitted, vec!["a"]);
+        assert_eq!(
+            exchange.requests()[1].oracle_pxs,
+            vec![("a:ABC".to_string(), "116".to_string())]
+        );
+
+        // 117 is within the band of the new reference.
+        assert_eq!(updater.tick().await?.submitted, vec!["a"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tick_survives_failed_submission() -> Result<()> {
+        let source = FakePriceSource {
+            rounds: Mutex::new(vec![
+                Ok(vec![price("a", "a:ABC", 100.0)]),
+                Ok(vec![price("a", "a:ABC", 105.0)]),
+            ]),
+        };
+        let exchange = MockExchange::default();
+        exchange.fail_next(1);
+        let mut updater = OracleUpdater::new(source, &exchange, OracleUpdaterConfig::default());
+
+        let tick = updater.tick().await?;
+        assert_eq!(tick.failed.len(), 1);
+        assert!(updater.last_prices.is_empty());
+
+        let tick = updater.tick().await?;
+        assert_eq!(tick.submitted, vec!["a"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,711 @@
+//! Pre-trade risk checks.
+//!
+//! A [`RiskChecker`] attached with `ExchangeClient::set_risk_checker` inspects
+//! every action when it is built, before it can be signed: typed, custom and
+//! raw actions alike, and the inner action of a multi-sig proposal. It checks
+//! orders, modifies and TWAPs against notional, position, price band, asset
+//! and rate limits, leverage changes against the leverage limit, and
+//! transfers and withdrawals against the transfer limit. Violations fail the
+//! build with [`Error::RiskCheck`].
+//!
+//! Mids and positions are not fetched by the checker. Feed them with
+//! [`RiskChecker::update_mids`] and [`RiskChecker::update_positions`].
+//!
+//! Setting an override lets violating actions through. Every action let
+//! through this way is logged and kept in [`RiskChecker::overrides`].
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use log::warn;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{
+    Error, Result,
+    exchange::{
+        ActionKind,
+        requests::{OrderRequest, UpdateLeverage},
+    },
+    info::UserStateResponse,
+    utils::now_timestamp_ms,
+};
+
+#[derive(Debug, Clone)]
+pub struct RiskConfig {
+    /// Largest notional of a single order at its limit price, in USDC. TWAPs
+    /// are valued at the mid and rejected when it is not known.
+    pub max_order_notional: Option<f64>,
+    /// Largest absolute position by asset id, if the order filled entirely.
+    /// Orders that reduce the position are always allowed.
+    pub max_position: HashMap<u32, f64>,
+    /// Largest relative distance between an order's limit price and the mid,
+    /// e.g. 0.05 for 5%. When set, orders on assets without a known mid are
+    /// rejected. TWAPs have no limit price and are not checked.
+    pub price_band: Option<f64>,
+    /// Asset ids that may be traded, or `None` for all.
+    pub allowed_assets: Option<HashSet<u32>>,
+    /// Most orders, counting each order of a bulk order or modify and each
+    /// TWAP, within `rate_window`.
+    pub max_orders: Option<u32>,
+    pub rate_window: Duration,
+    pub max_leverage: Option<u32>,
+    /// Largest amount of a single `usdSend`, `withdraw3`, `spotSend` or
+    /// `sendAsset`.
+    pub max_transfer: Option<f64>,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            max_order_notional: None,
+            max_position: HashMap::new(),
+            price_band: None,
+            allowed_assets: None,
+            max_orders: None,
+            rate_window: Duration::from_secs(1),
+            max_leverage: None,
+            max_transfer: None,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RiskViolation {
+    #[error("asset {asset} is not allowed")]
+    AssetNotAllowed { asset: u32 },
+    #[error("order notional {notional} on asset {asset} is above {max}")]
+    OrderNotional { asset: u32, notional: f64, max: f64 },
+    #[error("order size {sz} on asset {asset} is above {max}")]
+    OrderSize { asset: u32, sz: f64, max: f64 },
+    #[error("position on asset {asset} would reach {position}, above {max}")]
+    Position { asset: u32, position: f64, max: f64 },
+    #[error("price {price} on asset {asset} is more than {band} away from mid {mid}")]
+    PriceBand {
+        asset: u32,
+        price: f64,
+        mid: f64,
+        band: f64,
+    },
+    #[error("no mid for asset {asset} to check the order against")]
+    NoMid { asset: u32 },
+    #[error("more than {max} orders within {window:?}")]
+    OrderRate { max: u32, window: Duration },
+    #[error("leverage {leverage} on asset {asset} is above {max}")]
+    Leverage { asset: u32, leverage: u32, max: u32 },
+    #[error("transfer of {amount} is above {max}")]
+    Transfer { amount: f64, max: f64 },
+    #[error("invalid number {0}")]
+    InvalidNumber(String),
+}
+
+/// Action let through by an override.
+#[derive(Debug, Clone)]
+pub struct RiskOverride {
+    /// Time in milliseconds.
+    pub time: u64,
+    pub reason: String,
+    pub action: ActionKind,
+    pub violations: Vec<RiskViolation>,
+}
+
+#[derive(Debug)]
+pub struct RiskChecker {
+    config: RiskConfig,
+    mids: HashMap<u32, f64>,
+    positions: HashMap<u32, f64>,
+    /// Times of the orders within the rate window.
+    orders: VecDeque<Instant>,
+    override_reason: Option<String>,
+    overrides: Vec<RiskOverride>,
+}
+
+/// Action types the checker looks into. Raw actions of these types that do
+/// not parse as [`ActionKind`] are rejected rather than let through unchecked.
+const CHECKED_TYPES: [&str; 9] = [
+    "order",
+    "batchModify",
+    "twapOrder",
+    "updateLeverage",
+    "usdSend",
+    "withdraw3",
+    "spotSend",
+    "sendAsset",
+    "multiSig",
+];
+
+fn parse(value: &str) -> std::result::Result<f64, RiskViolation> {
+    value
+        .parse()
+        .map_err(|_| RiskViolation::InvalidNumber(value.to_string()))
+}
+
+/// Order an action places on the book, as the checks see it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PlacedOrder {
+    pub(crate) asset: u32,
+    pub(crate) is_buy: bool,
+    pub(crate) sz: f64,
+    /// Limit price, or `None` for a TWAP, which trades at the market.
+    pub(crate) limit_px: Option<f64>,
+}
+
+impl PlacedOrder {
+    fn new(order: &OrderRequest) -> std::result::Result<Self, RiskViolation> {
+        Ok(Self {
+            asset: order.asset,
+            is_buy: order.is_buy,
+            sz: parse(&order.sz)?,
+            limit_px: Some(parse(&order.limit_px)?),
+        })
+    }
+
+    /// Notional at the limit price, or at `mid` for a TWAP.
+    pub(crate) fn notional(&self, mid: Option<f64>) -> Option<f64> {
+        self.limit_px.or(mid).map(|price| price * self.sz)
+    }
+}
+
+/// Limits on orders and leverage changes, shared by [`RiskChecker`] and the
+/// signer's `Policy`.
+pub(crate) trait OrderLimits {
+    fn is_allowed(&self, asset: u32) -> bool;
+    fn max_notional(&self, asset: u32) -> Option<f64>;
+    fn max_size(&self, asset: u32) -> Option<f64>;
+    fn max_leverage(&self, asset: u32) -> Option<u32>;
+}
+
+impl OrderLimits for RiskConfig {
+    fn is_allowed(&self, asset: u32) -> bool {
+        self.allowed_assets
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(&asset))
+    }
+
+    fn max_notional(&self, _asset: u32) -> Option<f64> {
+        self.max_order_notional
+    }
+
+    fn max_size(&self, _asset: u32) -> Option<f64> {
+        None
+    }
+
+    fn max_leverage(&self, _asset: u32) -> Option<u32> {
+        self.max_leverage
+    }
+}
+
+/// Check a size and notional placed on `asset`, a single order's for the
+/// risk checker or an action's total for the signer. A `None` notional, for
+/// a TWAP without a mid, breaks any notional limit.
+pub(crate) fn check_placed(
+    limits: &impl OrderLimits,
+    asset: u32,
+    sz: f64,
+    notional: Option<f64>,
+    violations: &mut Vec<RiskViolation>,
+) {
+    if !limits.is_allowed(asset) {
+        violations.push(RiskViolation::AssetNotAllowed { asset });
+    }
+    if let Some(max) = limits.max_notional(asset) {
+        match notional {
+            Some(notional) if notional > max => violations.push(RiskViolation::OrderNotional {
+                asset,
+                notional,
+                max,
+            }),
+            Some(_) => {}
+            None => violations.push(RiskViolation::NoMid { asset }),
+        }
+    }
+    if let Some(max) = limits.max_size(asset)
+        && sz > max
+    {
+        violations.push(RiskViolation::OrderSize { asset, sz, max });
+    }
+}
+
+pub(crate) fn check_leverage(
+    limits: &impl OrderLimits,
+    update_leverage: &UpdateLeverage,
+    violations: &mut Vec<RiskViolation>,
+) {
+    let asset = update_leverage.asset;
+    if !limits.is_allowed(asset) {
+        violations.push(RiskViolation::AssetNotAllowed { asset });
+    }
+    if let Some(max) = limits.max_leverage(asset)
+        && update_leverage.leverage > max
+    {
+        violations.push(RiskViolation::Leverage {
+            asset,
+            leverage: update_leverage.leverage,
+            max,
+        });
+    }
+}
+
+/// Orders placed by an order, modify or TWAP action, in order. Other actions
+/// place none.
+pub(crate) fn placed_orders(
+    action: &ActionKind,
+) -> std::result::Result<Vec<PlacedOrder>, RiskViolation> {
+    match action {
+        ActionKind::Order(bulk_order) => bulk_order.orders.iter().map(PlacedOrder::new).collect(),
+        ActionKind::BatchModify(bulk_modify) => bulk_modify
+            .modifies
+            .iter()
+            .map(|modify| PlacedOrder::new(&modify.order))
+            .collect(),
+        ActionKind::TwapOrder(twap_order) => Ok(vec![PlacedOrder {
+            asset: twap_order.twap.asset,
+            is_buy: twap_order.twap.is_buy,
+            sz: parse(&twap_order.twap.sz)?,
+            limit_px: None,
+        }]),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Orders counted towards the rate limit, including those of the inner
+/// action of a `multiSig` action.
+fn order_count(action: &ActionKind) -> usize {
+    match action {
+        ActionKind::Order(bulk_order) => bulk_order.orders.len(),
+        ActionKind::BatchModify(bulk_modify) => bulk_modify.modifies.len(),
+        ActionKind::TwapOrder(_) => 1,
+        ActionKind::MultiSig(multi_sig) => order_count(&multi_sig.payload.action),
+        _ => 0,
+    }
+}
+
+impl RiskChecker {
+    pub fn new(config: RiskConfig) -> Self {
+        Self {
+            config,
+            mids: HashMap::new(),
+            positions: HashMap::new(),
+            orders: VecDeque::new(),
+            override_reason: None,
+            overrides: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &RiskConfig {
+        &self.config
+    }
+
+    pub fn set_mid(&mut self, asset: u32, mid: f64) {
+        self.mids.insert(asset, mid);
+    }
+
+    /// Mids by coin, as returned by `HyperliquidInfoClient::all_mids`.
+    /// Coins missing from `coin_to_asset` are skipped.
+    pub fn update_mids(
+        &mut self,
+        mids: &HashMap<String, String>,
+        coin_to_asset: &HashMap<String, u32>,
+    ) {
+        for (coin, mid) in mids {
+            if let (Some(asset), Ok(mid)) = (coin_to_asset.get(coin), mid.parse()) {
+                self.mids.insert(*asset, mid);
+            }
+        }
+    }
+
+    /// Signed position size of `asset`.
+    pub fn set_position(&mut self, asset: u32, szi: f64) {
+        self.positions.insert(asset, szi);
+    }
+
+    /// Replace all positions with those of `user_state`.
+    pub fn update_positions(
+        &mut self,
+        user_state: &UserStateResponse,
+        coin_to_asset: &HashMap<String, u32>,
+    ) {
+        self.positions.clear();
+        for asset_position in &user_state.asset_positions {
+            let position = &asset_position.position;
+            if let (Some(asset), Ok(szi)) =
+                (coin_to_asset.get(&position.coin), position.szi.parse())
+            {
+                self.positions.insert(*asset, szi);
+            }
+        }
+    }
+
+    /// Let violating actions through until cleared, recording each with
+    /// `reason`.
+    pub fn set_override(&mut self, reason: Option<String>) {
+        self.override_reason = reason;
+    }
+
+    /// Actions let through by an override, oldest first.
+    pub fn overrides(&self) -> &[RiskOverride] {
+        &self.overrides
+    }
+
+    /// Check `action`, counting its orders towards the rate limit when it
+    /// passes.
+    pub fn check(&mut self, action: &ActionKind) -> Result<()> {
+        let now = Instant::now();
+        while let Some(time) = self.orders.front()
+            && now.duration_since(*time) > self.config.rate_window
+        {
+            self.orders.pop_front();
+        }
+
+        let violations = self.violations(action);
+        if let Some(violation) = violations.first() {
+            let Some(reason) = &self.override_reason else {
+                return Err(Error::RiskCheck(violation.clone()));
+            };
+            warn!("risk override ({reason}): {violation}");
+            self.overrides.push(RiskOverride {
+                time: now_timestamp_ms(),
+                reason: reason.clone(),
+                action: action.clone(),
+                violations,
+            });
+        }
+
+        self.orders
+            .extend(std::iter::repeat_n(now, order_count(action)));
+        Ok(())
+    }
+
+    /// Check an untyped action, such as a [`RawAction`](crate::exchange::RawAction).
+    /// Actions that parse as [`ActionKind`] are checked like typed ones, and
+    /// actions of a type the checker looks into that do not parse are
+    /// rejected. Other actions pass.
+    pub fn check_raw(&mut self, action: &Value) -> Result<()> {
+        match serde_json::from_value::<ActionKind>(action.clone()) {
+            Ok(action) => self.check(&action),
+            Err(e) => match action["type"].as_str() {
+                Some(action_type) if CHECKED_TYPES.contains(&action_type) => Err(
+                    Error::GenericParse(format!("cannot risk check {action_type} action: {e}")),
+                ),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Every limit `action` breaks.
+    pub fn violations(&self, action: &ActionKind) -> Vec<RiskViolation> {
+        let mut violations = Vec::new();
+        match action {
+            ActionKind::Order(_) | ActionKind::BatchModify(_) | ActionKind::TwapOrder(_) => {
+                match placed_orders(action) {
+                    Ok(orders) => self.check_orders(&orders, &mut violations),
+                    Err(violation) => violations.push(violation),
+                }
+            }
+            ActionKind::UpdateLeverage(update_leverage) => {
+                check_leverage(&self.config, update_leverage, &mut violations)
+            }
+            ActionKind::UsdSend(usd_send) => self.check_transfer(&usd_send.amount, &mut violations),
+            ActionKind::Withdraw3(withdraw) => {
+                self.check_transfer(&withdraw.amount, &mut violations)
+            }
+            ActionKind::SpotSend(spot_send) => {
+                self.check_transfer(&spot_send.amount, &mut violations)
+            }
+            ActionKind::SendAsset(send_asset) => {
+                self.check_transfer(&send_asset.amount, &mut violations)
+            }
+            ActionKind::MultiSig(multi_sig) => return self.violations(&multi_sig.payload.action),
+            _ => {}
+        }
+        violations
+    }
+
+    fn check_orders(&self, orders: &[PlacedOrder], violations: &mut Vec<RiskViolation>) {
+        // Positions if every order so far filled entirely.
+        let mut positions = self.positions.clone();
+
+        for order in orders {
+            let mid = self.mids.get(&order.asset).copied();
+            check_placed(
+                &self.config,
+                order.asset,
+                order.sz,
+                order.notional(mid),
+                violations,
+            );
+
+            let position = positions.entry(order.asset).or_default();
+            let current = *position;
+            *position += if order.is_buy { order.sz } else { -order.sz };
+            if let Some(max) = self.config.max_position.get(&order.asset)
+                && position.abs() > *max
+                && position.abs() > current.abs()
+            {
+                violations.push(RiskViolation::Position {
+                    asset: order.asset,
+                    position: *position,
+                    max: *max,
+                });
+            }
+
+            if let (Some(band), Some(price)) = (self.config.price_band, order.limit_px) {
+                match self.mids.get(&order.asset) {
+                    Some(mid) if (price - mid).abs() > band * mid => {
+                        violations.push(RiskViolation::PriceBand {
+                            asset: order.asset,
+                            price,
+                            mid: *mid,
+                            band,
+                        })
+                    }
+                    Some(_) => {}
+                    None => violations.push(RiskViolation::NoMid { asset: order.asset }),
+                }
+            }
+        }
+
+        if let Some(max) = self.config.max_orders
+            && self.orders.len() + orders.len() > max as usize
+        {
+            violations.push(RiskViolation::OrderRate {
+                max,
+                window: self.config.rate_window,
+            });
+        }
+    }
+
+    fn check_transfer(&self, amount: &str, violations: &mut Vec<RiskViolation>) {
+        let Some(max) = self.config.max_transfer else {
+            return;
+        };
+        match parse(amount) {
+            Ok(amount) if amount > max => violations.push(RiskViolation::Transfer { amount, max }),
+            Ok(_) => {}
+            Err(violation) => violations.push(violation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        BaseUrl, ExchangeClient,
+        exchange::{MultiSigProposal, RawAction, builder::BuildAction},
+        test_utils::{action, limit_order, order, violation},
+    };
+
+    #[test]
+    fn test_order_limits() {
+        let mut checker = RiskChecker::new(RiskConfig {
+            max_order_notional: Some(10_000.0),
+            max_position: HashMap::from([(1, 2.0)]),
+            price_band: Some(0.05),
+            allowed_assets: Some(HashSet::from([0, 1])),
+            ..Default::default()
+        });
+        checker.set_mid(1, 3_000.0);
+        checker.set_position(1, 1.5);
+
+        assert!(checker.check(&order(1, true, "3000", "0.5")).is_ok());
+        assert!(matches!(
+            violation(checker.check(&order(1, true, "3000", "4"))),
+            RiskViolation::OrderNotional { .. }
+        ));
+        assert!(matches!(
+            violation(checker.check(&order(1, true, "3000", "1"))),
+            RiskViolation::Position { position, .. } if position == 2.5
+        ));
+        // Reducing a position above the limit is allowed.
+        checker.set_position(1, 3.0);
+        assert!(checker.check(&order(1, false, "3000", "0.5")).is_ok());
+
+        assert!(matches!(
+            violation(checker.check(&order(1, false, "2000", "0.1"))),
+            RiskViolation::PriceBand { .. }
+        ));
+        assert_eq!(
+            violation(checker.check(&order(0, true, "90000", "0.1"))),
+            RiskViolation::NoMid { asset: 0 }
+        );
+        assert_eq!(
+            violation(checker.check(&order(2, true, "150", "1"))),
+            RiskViolation::AssetNotAllowed { asset: 2 }
+        );
+    }
+
+    #[test]
+    fn test_build_checks_actions() -> Result<()> {
+        let mut client = ExchangeClient::new(Some(BaseUrl::Testnet), None, HashMap::new())?;
+        let checker = Arc::new(Mutex::new(RiskChecker::new(RiskConfig {
+            max_order_notional: Some(1_000.0),
+            ..Default::default()
+        })));
+        client.set_risk_checker(Some(checker.clone()));
+
+        assert!(order(0, true, "100", "1").build(&client).is_ok());
+        assert!(matches!(
+            order(0, true, "100", "20").build(&client),
+            Err(Error::RiskCheck(RiskViolation::OrderNotional { .. }))
+        ));
+
+        checker
+            .lock()
+            .unwrap()
+            .set_override(Some("manual hedge".to_string()));
+        assert!(order(0, true, "100", "20").build(&client).is_ok());
+        assert_eq!(checker.lock().unwrap().overrides().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_twap_limits() {
+        let mut checker = RiskChecker::new(RiskConfig {
+            max_order_notional: Some(1_000.0),
+            max_position: HashMap::from([(0, 8.0)]),
+            allowed_assets: Some(HashSet::from([0])),
+            ..Default::default()
+        });
+        let twap = |asset: u32, sz: &str| {
+            action(json!({
+                "type": "twapOrder",
+                "twap": { "a": asset, "b": true, "s": sz, "r": false, "m": 10, "t": false },
+            }))
+        };
+
+        // TWAPs are valued at the mid.
+        assert_eq!(
+            violation(checker.check(&twap(0, "5"))),
+            RiskViolation::NoMid { asset: 0 }
+        );
+        checker.set_mid(0, 100.0);
+        assert!(checker.check(&twap(0, "5")).is_ok());
+        assert!(matches!(
+            violation(checker.check(&twap(0, "20"))),
+            RiskViolation::OrderNotional { notional, .. } if notional == 2_000.0
+        ));
+        assert!(matches!(
+            violation(checker.check(&twap(0, "9"))),
+            RiskViolation::Position { position, .. } if position == 9.0
+        ));
+        assert_eq!(
+            violation(checker.check(&twap(1, "1"))),
+            RiskViolation::AssetNotAllowed { asset: 1 }
+        );
+    }
+
+    #[test]
+    fn test_build_checks_raw_and_multi_sig_actions() -> Result<()> {
+        let mut client = ExchangeClient::new(Some(BaseUrl::Testnet), None, HashMap::new())?;
+        client.set_risk_checker(Some(Arc::new(Mutex::new(RiskChecker::new(RiskConfig {
+            max_order_notional: Some(1_000.0),
+            ..Default::default()
+        })))));
+        let raw_order = |sz: &str| {
+            RawAction::L1(json!({
+                "type": "order",
+                "orders": [limit_order(0, true, "100", sz, "Gtc")],
+                "grouping": "na",
+            }))
+        };
+
+        assert!(raw_order("1").build(&client).is_ok());
+        assert!(matches!(
+            raw_order("20").build(&client),
+            Err(Error::RiskCheck(RiskViolation::OrderNotional { .. }))
+        ));
+        // An order the checker cannot read is not let through.
+        let unreadable = RawAction::L1(json!({ "type": "order", "orders": [{ "a": 0 }] }));
+        assert!(matches!(
+            unreadable.build(&client),
+            Err(Error::GenericParse(_))
+        ));
+        assert!(
+            RawAction::L1(json!({ "type": "somethingNew" }))
+                .build(&client)
+                .is_ok()
+        );
+
+        let wallet = PrivateKeySigner::random();
+        let proposal = MultiSigProposal::new(
+            &client,
+            Address::repeat_byte(0x42),
+            wallet.address(),
+            order(0, true, "100", "20"),
+        )?;
+        let signature = proposal.sign(&wallet)?;
+        assert!(matches!(
+            proposal.aggregate(&client, &[signature]),
+            Err(Error::RiskCheck(RiskViolation::OrderNotional { .. }))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_rate() {
+        let mut checker = RiskChecker::new(RiskConfig {
+            max_orders: Some(2),
+            rate_window: Duration::from_secs(60),
+            ..Default::default()
+        });
+
+        assert!(checker.check(&order(0, true, "1", "1")).is_ok());
+        assert!(checker.check(&order(0, true, "1", "1")).is_ok());
+        assert!(matches!(
+            violation(checker.check(&order(0, true, "1", "1"))),
+            RiskViolation::OrderRate { max: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_leverage_transfer_and_override() {
+        let mut checker = RiskChecker::new(RiskConfig {
+            max_leverage: Some(10),
+            max_transfer: Some(1_000.0),
+            ..Default::default()
+        });
+        let leverage = action(
+            json!({ "type": "updateLeverage", "asset": 0, "isCross": true, "leverage": 20 }),
+        );
+        let withdraw = action(json!({
+            "type": "withdraw3",
+            "signatureChainId": "0x66eee",
+            "hyperliquidChain": "Testnet",
+            "destination": "0x1111111111111111111111111111111111111111",
+            "amount": "5000",
+            "time": 1,
+        }));
+
+        assert!(matches!(
+            violation(checker.check(&leverage)),
+            RiskViolation::Leverage { max: 10, .. }
+        ));
+        assert_eq!(
+            violation(checker.check(&withdraw)),
+            RiskViolation::Transfer {
+                amount: 5_000.0,
+                max: 1_000.0
+            }
+        );
+
+        checker.set_override(Some("treasury rebalance".to_string()));
+        assert!(checker.check(&withdraw).is_ok());
+        checker.set_override(None);
+        assert!(checker.check(&withdraw).is_err());
+
+        assert_eq!(checker.overrides().len(), 1);
+        assert_eq!(checker.overrides()[0].reason, "treasury rebalance");
+        assert!(matches!(
+            checker.overrides()[0].violations[..],
+            [RiskViolation::Transfer { .. }]
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+//! Signing in a separate process that holds the keys.
+//!
+//! The `hl-signer` binary (features `cli` and `signer`) serves
+//! [`SignerServer`] on localhost. Strategies build actions as usual and sign
+//! them with a [`RemoteSigner`], which posts the unsigned action to the
+//! server. The server recomputes the signing hash from the action itself,
+//! refuses user-signed actions whose `hyperliquidChain` is not its network,
+//! checks the action against its [`Policy`] and returns the signature.
+//!
+//! ```no_run
+//! # async fn example(client: hl_rs::ExchangeClient, action: hl_rs::exchange::ActionKind) -> hl_rs::Result<()> {
+//! use hl_rs::{exchange::builder::BuildAction, signer::RemoteSigner};
+//!
+//! let signer = RemoteSigner::new("http://127.0.0.1:7575", None);
+//! let status = action.build(&client)?.sign_with(&signer).await?.send().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod policy;
+mod remote;
+#[cfg(feature = "signer")]
+mod server;
+
+use alloy::primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::exchange::WireSignature;
+
+pub use policy::{AssetLimits, Policy};
+pub use remote::RemoteSigner;
+#[cfg(feature = "signer")]
+pub use server::{SignerConfig, SignerServer};
+
+/// Body of `POST /sign`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignRequest {
+    action: Value,
+    nonce: u64,
+    vault_address: Option<Address>,
+    /// Hash the client expects to be signed, compared with the one the
+    /// server computes to catch network mismatches.
+    signing_hash: B256,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SignResponse {
+    signature: WireSignature,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddressResponse {
+    address: Address,
+}
+
+/// Body of every error response.
+#[derive(Serialize, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
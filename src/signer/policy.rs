@@ -0,0 +1,238 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, Result,
+    exchange::ActionKind,
+    risk::{OrderLimits, PlacedOrder, RiskViolation, check_leverage, check_placed, placed_orders},
+};
+
+/// What a signer agrees to sign. Anything not explicitly allowed is refused.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policy {
+    /// Action types that may be signed, as in their `type` field, e.g.
+    /// `"order"` or `"withdraw3"`.
+    pub allowed_actions: Vec<String>,
+    /// Largest notional at their limit prices of the orders one action
+    /// places on an asset, in USDC.
+    pub max_notional: Option<f64>,
+    /// Destinations `withdraw3`, `usdSend`, `spotSend` and `sendAsset` may
+    /// send to.
+    pub withdrawal_allowlist: Vec<Address>,
+    /// Limits by asset id. When not empty, orders and leverage changes on
+    /// other assets are refused.
+    pub assets: HashMap<u32, AssetLimits>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssetLimits {
+    /// Largest notional at their limit prices of the orders one action
+    /// places on the asset, in USDC.
+    pub max_notional: Option<f64>,
+    /// Largest total size of the orders one action places on the asset.
+    pub max_size: Option<f64>,
+    pub max_leverage: Option<u32>,
+}
+
+fn violation(message: String) -> Error {
+    Error::PolicyViolation(message)
+}
+
+impl Policy {
+    /// Policy from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let policy =
+            std::fs::read_to_string(path).map_err(|e| Error::GenericReader(e.to_string()))?;
+        serde_json::from_str(&policy).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    /// Check that `action` may be signed. The inner action of a `multiSig`
+    /// action is checked as well.
+    pub fn check(&self, action: &ActionKind) -> Result<()> {
+        let value = serde_json::to_value(action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let action_type = value["type"].as_str().unwrap_or_default();
+        if !self
+            .allowed_actions
+            .iter()
+            .any(|allowed| allowed == action_type)
+        {
+            return Err(violation(format!("{action_type} actions are not allowed")));
+        }
+
+        let mut violations = Vec::new();
+        match action {
+            ActionKind::Order(_) | ActionKind::BatchModify(_) | ActionKind::TwapOrder(_) => {
+                let orders = placed_orders(action).map_err(|e| violation(e.to_string()))?;
+                self.check_orders(&orders, &mut violations);
+            }
+            ActionKind::UpdateLeverage(update_leverage) => {
+                check_leverage(self, update_leverage, &mut violations)
+            }
+            ActionKind::Withdraw3(withdraw) => self.check_destination(&withdraw.destination)?,
+            ActionKind::UsdSend(usd_send) => self.check_destination(&usd_send.destination)?,
+            ActionKind::SpotSend(spot_send) => self.check_destination(&spot_send.destination)?,
+            ActionKind::SendAsset(send_asset) => self.check_destination(&send_asset.destination)?,
+            ActionKind::MultiSig(multi_sig) => self.check(&multi_sig.payload.action)?,
+            _ => {}
+        }
+
+        match violations.first() {
+            Some(first) => Err(violation(first.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Limits apply to the total size and notional per asset of the orders,
+    /// so splitting an order into many does not get around them. The signer
+    /// knows no mids, so TWAPs are refused when a notional limit applies.
+    fn check_orders(&self, orders: &[PlacedOrder], violations: &mut Vec<RiskViolation>) {
+        let mut totals: BTreeMap<u32, (f64, Option<f64>)> = BTreeMap::new();
+        for order in orders {
+            let (sz, notional) = totals.entry(order.asset).or_insert((0.0, Some(0.0)));
+            *sz += order.sz;
+            *notional = notional
+                .zip(order.notional(None))
+                .map(|(total, notional)| total + notional);
+        }
+
+        for (asset, (sz, notional)) in totals {
+            check_placed(self, asset, sz, notional, violations);
+        }
+    }
+
+    fn check_destination(&self, destination: &str) -> Result<()> {
+        let destination: Address = destination
+            .parse()
+            .map_err(|_| violation(format!("invalid destination {destination}")))?;
+        if self.withdrawal_allowlist.contains(&destination) {
+            Ok(())
+        } else {
+            Err(violation(format!(
+                "destination {destination} is not allowlisted"
+            )))
+        }
+    }
+}
+
+/// Assets missing from a non-empty `assets` map are not allowed. Both the
+/// global and the asset's notional limits apply.
+impl OrderLimits for Policy {
+    fn is_allowed(&self, asset: u32) -> bool {
+        self.assets.is_empty() || self.assets.contains_key(&asset)
+    }
+
+    fn max_notional(&self, asset: u32) -> Option<f64> {
+        let asset_max = self
+            .assets
+            .get(&asset)
+            .and_then(|limits| limits.max_notional);
+        match (self.max_notional, asset_max) {
+            (Some(max), Some(asset_max)) => Some(max.min(asset_max)),
+            (max, asset_max) => max.or(asset_max),
+        }
+    }
+
+    fn max_size(&self, asset: u32) -> Option<f64> {
+        self.assets.get(&asset).and_then(|limits| limits.max_size)
+    }
+
+    fn max_leverage(&self, asset: u32) -> Option<u32> {
+        self.assets
+            .get(&asset)
+            .and_then(|limits| limits.max_leverage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_utils::{self, action, bulk_order, limit_order, rejected};
+
+    fn policy() -> Policy {
+        serde_json::from_value(json!({
+            "allowedActions": ["order", "cancel", "updateLeverage", "withdraw3", "twapOrder"],
+            "maxNotional": 50000.0,
+            "withdrawalAllowlist": ["0x1111111111111111111111111111111111111111"],
+            "assets": {
+                "0": { "maxSize": 0.5, "maxLeverage": 10 },
+                "1": { "maxNotional": 10000.0 },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn order(asset: u32, px: &str, sz: &str) -> ActionKind {
+        test_utils::order(asset, true, px, sz)
+    }
+
+    fn refused(result: Result<()>) -> String {
+        rejected(result).to_string()
+    }
+
+    #[test]
+    fn test_check_orders() {
+        let policy = policy();
+        assert!(policy.check(&order(0, "90000", "0.5")).is_ok());
+        assert!(refused(policy.check(&order(0, "90000", "0.6"))).contains("above 50000"));
+        assert!(refused(policy.check(&order(0, "10", "0.6"))).contains("size"));
+        assert!(policy.check(&order(1, "3000", "3")).is_ok());
+        assert!(refused(policy.check(&order(1, "3000", "4"))).contains("above 10000"));
+        assert!(refused(policy.check(&order(2, "150", "1"))).contains("not allowed"));
+
+        // Orders split under the limits are checked on their totals.
+        let split =
+            |count: usize| bulk_order(vec![limit_order(1, true, "3000", "3", "Gtc"); count]);
+        assert!(policy.check(&split(1)).is_ok());
+        assert!(refused(policy.check(&split(2))).contains("18000 on asset 1 is above 10000"));
+
+        let twap = action(json!({
+            "type": "twapOrder",
+            "twap": { "a": 0, "b": true, "s": "0.1", "r": false, "m": 10, "t": false },
+        }));
+        assert!(refused(policy.check(&twap)).contains("no mid for asset 0"));
+    }
+
+    #[test]
+    fn test_check_actions() {
+        let policy = policy();
+        let leverage = |leverage: u32| {
+            action(
+                json!({ "type": "updateLeverage", "asset": 0, "isCross": true, "leverage": leverage }),
+            )
+        };
+        assert!(policy.check(&leverage(10)).is_ok());
+        assert!(refused(policy.check(&leverage(20))).contains("above 10"));
+
+        let withdraw = |destination: &str| {
+            action(json!({
+                "type": "withdraw3",
+                "signatureChainId": "0x66eee",
+                "hyperliquidChain": "Testnet",
+                "destination": destination,
+                "amount": "100",
+                "time": 1,
+            }))
+        };
+        assert!(
+            policy
+                .check(&withdraw("0x1111111111111111111111111111111111111111"))
+                .is_ok()
+        );
+        assert!(
+            refused(policy.check(&withdraw("0x2222222222222222222222222222222222222222")))
+                .contains("not allowlisted")
+        );
+
+        let noop = action(json!({ "type": "noop" }));
+        assert!(refused(policy.check(&noop)).contains("noop actions are not allowed"));
+    }
+}
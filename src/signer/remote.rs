@@ -0,0 +1,83 @@
+use alloy::primitives::{Address, Signature};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    Error, Result,
+    exchange::{Action, ActionSigner},
+    signer::{AddressResponse, ErrorResponse, SignRequest, SignResponse},
+};
+
+/// Client of an `hl-signer` server.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl RemoteSigner {
+    /// Signer served at `url`, authenticating with `token` when the server
+    /// requires one.
+    pub fn new(url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::default(),
+            url: url.into(),
+            token,
+        }
+    }
+
+    /// Address of the key the server signs with.
+    pub async fn address(&self) -> Result<Address> {
+        let response: AddressResponse = self
+            .send(self.client.get(format!("{}/address", self.url)))
+            .await?;
+        Ok(response.address)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::RemoteSigner(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Error::RemoteSigner(e.to_string()))?;
+
+        if status.is_success() {
+            return serde_json::from_str(&text).map_err(|e| Error::JsonParse(e.to_string()));
+        }
+        let message = serde_json::from_str::<ErrorResponse>(&text)
+            .map(|error| error.error)
+            .unwrap_or(text);
+        match status {
+            StatusCode::FORBIDDEN => Err(Error::PolicyViolation(message)),
+            _ => Err(Error::RemoteSigner(format!("{status}: {message}"))),
+        }
+    }
+}
+
+impl ActionSigner for RemoteSigner {
+    async fn sign_action(&self, action: &Action) -> Result<Signature> {
+        let request = SignRequest {
+            action: action.action.clone(),
+            nonce: action.nonce,
+            vault_address: action.vault_address,
+            signing_hash: action.signing_data.signing_hash(),
+        };
+        let response: SignResponse = self
+            .send(
+                self.client
+                    .post(format!("{}/sign", self.url))
+                    .json(&request),
+            )
+            .await?;
+        response.signature.into_signature()
+    }
+}
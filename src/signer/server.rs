@@ -0,0 +1,186 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use alloy::signers::{SignerSync, local::PrivateKeySigner};
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing,
+};
+use log::{info, warn};
+use serde_json::{Value, json};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{
+    Error, Result,
+    exchange::decode_payload,
+    signer::{AddressResponse, ErrorResponse, Policy, SignRequest, SignResponse},
+    utils::hyperliquid_chain,
+};
+
+#[derive(Debug, Clone)]
+pub struct SignerConfig {
+    pub wallet: PrivateKeySigner,
+    pub policy: Policy,
+    /// Network the signed actions are for, which L1 signatures depend on.
+    pub is_mainnet: bool,
+    /// Bearer token clients must send, if any.
+    pub token: Option<String>,
+    /// Address to listen on. Port 0 picks any free port.
+    pub addr: SocketAddr,
+}
+
+/// Running signer server, stopped when dropped.
+pub struct SignerServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl SignerServer {
+    pub async fn start(config: SignerConfig) -> Result<Self> {
+        let listener = TcpListener::bind(config.addr)
+            .await
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+
+        let app = Router::new()
+            .route("/address", routing::get(address))
+            .route("/sign", routing::post(sign))
+            .with_state(Arc::new(config));
+        let task = tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        Ok(Self { addr, task })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for SignerServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+type SharedConfig = Arc<SignerConfig>;
+
+fn error(status: StatusCode, error: String) -> Response {
+    (status, Json(ErrorResponse { error })).into_response()
+}
+
+/// Compare the bearer token without returning early on the first
+/// mismatching byte.
+fn authorized(config: &SignerConfig, headers: &HeaderMap) -> bool {
+    let Some(token) = &config.token else {
+        return true;
+    };
+    let Some(sent) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    sent.len() == token.len()
+        && sent
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn address(State(config): State<SharedConfig>, headers: HeaderMap) -> Response {
+    if !authorized(&config, &headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid token".to_string());
+    }
+
+    Json(AddressResponse {
+        address: config.wallet.address(),
+    })
+    .into_response()
+}
+
+async fn sign(
+    State(config): State<SharedConfig>,
+    headers: HeaderMap,
+    Json(request): Json<SignRequest>,
+) -> Response {
+    if !authorized(&config, &headers) {
+        return error(StatusCode::UNAUTHORIZED, "invalid token".to_string());
+    }
+
+    let action_type = request.action["type"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+    match check(&config, request) {
+        Ok(response) => {
+            info!("signed {action_type}");
+            Json(response).into_response()
+        }
+        Err(err) => {
+            warn!("refused {action_type}: {err}");
+            match err {
+                Error::PolicyViolation(message) => error(StatusCode::FORBIDDEN, message),
+                err => error(StatusCode::BAD_REQUEST, err.to_string()),
+            }
+        }
+    }
+}
+
+/// Recompute the signing hash from the action, so the client cannot get an
+/// arbitrary hash signed, then check the policy and sign.
+fn check(config: &SignerConfig, request: SignRequest) -> Result<SignResponse> {
+    let payload = json!({
+        "action": request.action,
+        "nonce": request.nonce,
+        "signature": null,
+        "vaultAddress": request.vault_address,
+    });
+    let decoded = decode_payload(&payload.to_string(), config.is_mainnet)?;
+    if !decoded.round_trips {
+        return Err(Error::GenericParse(
+            "action does not serialize back to the same JSON".to_string(),
+        ));
+    }
+    let chain = hyperliquid_chain(config.is_mainnet);
+    if decoded.signing_hash != request.signing_hash {
+        return Err(Error::GenericParse(format!(
+            "signing hash mismatch, the signer is on {chain}"
+        )));
+    }
+    check_chain(&request.action, chain)?;
+
+    config.policy.check(&decoded.action)?;
+    let signature = config
+        .wallet
+        .sign_hash_sync(&decoded.signing_hash)
+        .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+    Ok(SignResponse {
+        signature: signature.into(),
+    })
+}
+
+/// User-signed actions name their network in `hyperliquidChain` rather than
+/// through the signing hash, so check it, and that of the inner action of a
+/// `multiSig` action, against the signer's network.
+fn check_chain(action: &Value, chain: &str) -> Result<()> {
+    if let Some(action_chain) = action.get("hyperliquidChain")
+        && action_chain != chain
+    {
+        return Err(Error::GenericParse(format!(
+            "action is for {action_chain}, the signer is on {chain}"
+        )));
+    }
+    match action.pointer("/payload/action") {
+        Some(inner) => check_chain(inner, chain),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,98 @@
+//! Fixtures shared by the unit tests.
+
+use std::sync::Mutex;
+
+use serde_json::{Value, json};
+
+use crate::{
+    Error, Result,
+    dead_mans_switch::CancelScheduler,
+    exchange::{ActionKind, requests::SetOracle},
+    oracle::OracleSubmitter,
+    risk::RiskViolation,
+};
+
+pub(crate) fn action(value: Value) -> ActionKind {
+    serde_json::from_value(value).unwrap()
+}
+
+/// Limit order in its wire form.
+pub(crate) fn limit_order(asset: u32, is_buy: bool, px: &str, sz: &str, tif: &str) -> Value {
+    json!({ "a": asset, "b": is_buy, "p": px, "s": sz, "r": false, "t": { "limit": { "tif": tif } } })
+}
+
+pub(crate) fn bulk_order(orders: Vec<Value>) -> ActionKind {
+    action(json!({ "type": "order", "orders": orders, "grouping": "na" }))
+}
+
+/// `order` action with a single good-til-cancelled limit order.
+pub(crate) fn order(asset: u32, is_buy: bool, px: &str, sz: &str) -> ActionKind {
+    bulk_order(vec![limit_order(asset, is_buy, px, sz, "Gtc")])
+}
+
+/// Error a risk checker or signer policy rejected an action with.
+#[track_caller]
+pub(crate) fn rejected(result: Result<()>) -> Error {
+    match result {
+        Err(error @ (Error::RiskCheck(_) | Error::PolicyViolation(_))) => error,
+        result => panic!("expected a rejection, got {result:?}"),
+    }
+}
+
+#[track_caller]
+pub(crate) fn violation(result: Result<()>) -> RiskViolation {
+    match rejected(result) {
+        Error::RiskCheck(violation) => violation,
+        error => panic!("expected a risk violation, got {error:?}"),
+    }
+}
+
+/// Exchange stand-in for the traits background tasks send requests through.
+/// It records the requests it accepts and can be told to fail the next ones.
+pub(crate) struct MockExchange<T> {
+    failures: Mutex<usize>,
+    requests: Mutex<Vec<T>>,
+}
+
+impl<T> Default for MockExchange<T> {
+    fn default() -> Self {
+        Self {
+            failures: Mutex::new(0),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<T: Clone> MockExchange<T> {
+    /// Fail the next `count` requests with a timeout.
+    pub(crate) fn fail_next(&self, count: usize) {
+        *self.failures.lock().unwrap() = count;
+    }
+
+    /// Accepted requests, oldest first.
+    pub(crate) fn requests(&self) -> Vec<T> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn record(&self, request: T) -> Result<()> {
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            return Err(Error::GenericRequest("timeout".to_string()));
+        }
+        self.requests.lock().unwrap().push(request);
+        Ok(())
+    }
+}
+
+impl OracleSubmitter for &MockExchange<SetOracle> {
+    async fn submit(&self, set_oracle: SetOracle) -> Result<()> {
+        self.record(set_oracle)
+    }
+}
+
+impl CancelScheduler for &MockExchange<Option<u64>> {
+    async fn schedule_cancel(&self, time: Option<u64>) -> Result<()> {
+        self.record(time)
+    }
+}
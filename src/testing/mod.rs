@@ -0,0 +1,164 @@
+//! In-process mock of the Hyperliquid API for offline integration tests.
+//!
+//! [`MockServer`] serves `/info`, `/exchange` and `/ws` on localhost. It
+//! decodes exchange payloads, recovers their signers and checks them against
+//! the configured accounts and the agents they approved, and keeps a simple
+//! book: orders fill at the mid when they cross it and rest otherwise. The
+//! server signs as testnet, so clients must not point at mainnet.
+//!
+//! ```no_run
+//! # async fn example() -> hl_rs::Result<()> {
+//! use alloy::signers::local::PrivateKeySigner;
+//! use hl_rs::testing::{MockConfig, MockServer};
+//!
+//! let wallet = PrivateKeySigner::random();
+//! let server = MockServer::start(MockConfig {
+//!     accounts: vec![(wallet.address(), 10_000.0)],
+//!     ..Default::default()
+//! })
+//! .await?;
+//!
+//! let exchange_client = server.exchange_client(None)?;
+//! let info_client = server.info_client()?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod state;
+mod ws;
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use alloy::primitives::Address;
+use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing};
+use serde_json::Value;
+use tokio::{net::TcpListener, sync::broadcast, task::JoinHandle};
+
+use crate::{
+    BaseUrl, Error, ExchangeClient, Result, info::HyperliquidInfoClient, testing::state::MockState,
+};
+
+pub(crate) type SharedState = Arc<Mutex<MockState>>;
+
+#[derive(Debug, Clone)]
+pub struct MockConfig {
+    /// Accounts allowed to sign, with their starting USDC balance.
+    pub accounts: Vec<(Address, f64)>,
+    /// Perp coins and their mids. Asset ids follow the order of the list.
+    pub coins: Vec<(String, f64)>,
+    /// Spot pairs listed by `spotMeta`, as "BASE/USDC", with asset ids from
+    /// 10000 in the order of the list. Orders on them are refused.
+    pub spot_pairs: Vec<String>,
+    /// Port to listen on, 0 for any free port.
+    pub port: u16,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            accounts: vec![],
+            coins: vec![
+                ("BTC".to_string(), 100_000.0),
+                ("ETH".to_string(), 3_000.0),
+                ("SOL".to_string(), 150.0),
+            ],
+            spot_pairs: vec!["PURR/USDC".to_string()],
+            port: 0,
+        }
+    }
+}
+
+/// Running mock server, stopped when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: SharedState,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start(config: MockConfig) -> Result<Self> {
+        let (events, _) = broadcast::channel(1024);
+        let state = Arc::new(Mutex::new(MockState::new(&config, events)));
+
+        let listener = TcpListener::bind(("127.0.0.1", config.port))
+            .await
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+
+        let app = Router::new()
+            .route("/info", routing::post(info))
+            .route("/exchange", routing::post(exchange))
+            .route("/ws", routing::get(ws::upgrade))
+            .with_state(state.clone());
+        let task = tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        Ok(Self { addr, state, task })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}/ws", self.addr)
+    }
+
+    /// Exchange client pointed at the server, with the configured coins and
+    /// spot pairs.
+    pub fn exchange_client(&self, vault_address: Option<Address>) -> Result<ExchangeClient> {
+        let coin_to_asset = self.state.lock().unwrap().coin_to_asset();
+        let mut client =
+            ExchangeClient::new(Some(BaseUrl::Localhost), vault_address, coin_to_asset)?;
+        client.http_client_mut().base_url = self.url();
+        Ok(client)
+    }
+
+    pub fn info_client(&self) -> Result<HyperliquidInfoClient> {
+        let mut client = HyperliquidInfoClient::new(Some(BaseUrl::Localhost))?;
+        client.http_client_mut().base_url = self.url();
+        Ok(client)
+    }
+
+    /// USDC balance of `user`, excluding unrealized pnl.
+    pub fn balance(&self, user: Address) -> Option<f64> {
+        self.state.lock().unwrap().balance(user)
+    }
+
+    /// Move the mid of `coin`, pushing `allMids` to subscribers. Resting
+    /// orders are not matched against the new mid.
+    pub fn set_mid(&self, coin: &str, mid: f64) {
+        self.state.lock().unwrap().set_mid(coin, mid);
+    }
+
+    /// Bodies of the `/exchange` requests received so far.
+    pub fn exchange_requests(&self) -> Vec<Value> {
+        self.state.lock().unwrap().requests().to_vec()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn info(State(state): State<SharedState>, body: String) -> impl IntoResponse {
+    match state.lock().unwrap().info(&body) {
+        Ok(response) => Json(response).into_response(),
+        Err(err) => (StatusCode::UNPROCESSABLE_ENTITY, err).into_response(),
+    }
+}
+
+async fn exchange(State(state): State<SharedState>, body: String) -> impl IntoResponse {
+    match state.lock().unwrap().exchange(&body) {
+        Ok(response) => Json(response).into_response(),
+        Err(err) => (StatusCode::UNPROCESSABLE_ENTITY, err).into_response(),
+    }
+}
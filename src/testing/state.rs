@@ -0,0 +1,790 @@
+use std::collections::{HashMap, HashSet};
+
+use alloy::primitives::{Address, Signature, U256};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::sync::broadcast;
+
+use crate::{
+    exchange::{
+        ActionKind, l1_action_hash,
+        requests::{BulkOrder, Order, OrderRequest},
+    },
+    testing::MockConfig,
+    utils::{float_to_string_for_hashing, l1_action_signing_hash, now_timestamp_ms, spot_asset_id},
+};
+
+const DEFAULT_LEVERAGE: u32 = 20;
+const MAX_LEVERAGE: u32 = 50;
+
+/// Change pushed to websocket subscribers.
+#[derive(Debug, Clone)]
+pub(crate) enum WsEvent {
+    AllMids,
+    OrderUpdate { user: Address, update: Value },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeRequest {
+    action: Value,
+    nonce: u64,
+    signature: WireSignature,
+    vault_address: Option<Address>,
+}
+
+#[derive(Deserialize)]
+struct WireSignature {
+    r: U256,
+    s: U256,
+    v: u64,
+}
+
+#[derive(Debug, Default)]
+struct Account {
+    balance: f64,
+    leverage: HashMap<u32, u32>,
+}
+
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    oid: u64,
+    user: Address,
+    asset: u32,
+    is_buy: bool,
+    limit_px: f64,
+    sz: f64,
+    cloid: Option<String>,
+    timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    szi: f64,
+    entry_px: f64,
+}
+
+#[derive(Debug)]
+struct Agent {
+    master: Address,
+    name: String,
+    valid_until: Option<u64>,
+}
+
+/// Exchange state behind the mock server.
+pub(crate) struct MockState {
+    coins: Vec<(String, f64)>,
+    spot_pairs: Vec<String>,
+    accounts: HashMap<Address, Account>,
+    agents: HashMap<Address, Agent>,
+    builder_fees: HashMap<(Address, Address), u64>,
+    orders: Vec<RestingOrder>,
+    positions: HashMap<(Address, u32), Position>,
+    nonces: HashSet<(Address, u64)>,
+    next_oid: u64,
+    fills: Vec<(Address, Value)>,
+    requests: Vec<Value>,
+    events: broadcast::Sender<WsEvent>,
+}
+
+fn hex(address: Address) -> String {
+    address.to_string().to_lowercase()
+}
+
+fn wire(x: f64) -> String {
+    float_to_string_for_hashing(x)
+}
+
+impl MockState {
+    pub(crate) fn new(config: &MockConfig, events: broadcast::Sender<WsEvent>) -> Self {
+        Self {
+            coins: config.coins.clone(),
+            spot_pairs: config.spot_pairs.clone(),
+            accounts: config
+                .accounts
+                .iter()
+                .map(|&(user, balance)| {
+                    (
+                        user,
+                        Account {
+                            balance,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            agents: HashMap::new(),
+            builder_fees: HashMap::new(),
+            orders: vec![],
+            positions: HashMap::new(),
+            nonces: HashSet::new(),
+            next_oid: 1,
+            fills: vec![],
+            requests: vec![],
+            events,
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<WsEvent> {
+        self.events.subscribe()
+    }
+
+    pub(crate) fn requests(&self) -> &[Value] {
+        &self.requests
+    }
+
+    pub(crate) fn coin_to_asset(&self) -> HashMap<String, u32> {
+        let perps = self
+            .coins
+            .iter()
+            .enumerate()
+            .map(|(asset, (coin, _))| (coin.clone(), asset as u32));
+        let spot = self
+            .spot_pairs
+            .iter()
+            .enumerate()
+            .map(|(index, pair)| (pair.clone(), spot_asset_id(index as u32)));
+        perps.chain(spot).collect()
+    }
+
+    /// Spot universe with USDC as token 0 and the base of the `i`-th pair as
+    /// token `i + 1`.
+    fn spot_meta(&self) -> Value {
+        let bases = self
+            .spot_pairs
+            .iter()
+            .map(|pair| pair.split_once('/').map_or(pair.as_str(), |(base, _)| base));
+        let tokens: Vec<_> = std::iter::once("USDC")
+            .chain(bases)
+            .enumerate()
+            .map(|(index, name)| {
+                json!({
+                    "name": name,
+                    "szDecimals": 2,
+                    "weiDecimals": 8,
+                    "index": index,
+                    "tokenId": format!("{index:#034x}"),
+                    "isCanonical": true,
+                })
+            })
+            .collect();
+        let universe: Vec<_> = self
+            .spot_pairs
+            .iter()
+            .enumerate()
+            .map(|(index, pair)| {
+                json!({ "name": pair, "tokens": [index + 1, 0], "index": index, "isCanonical": true })
+            })
+            .collect();
+        json!({ "universe": universe, "tokens": tokens })
+    }
+
+    pub(crate) fn mids(&self) -> Value {
+        self.coins
+            .iter()
+            .map(|(coin, mid)| (coin.clone(), Value::from(wire(*mid))))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    pub(crate) fn set_mid(&mut self, coin: &str, mid: f64) {
+        if let Some((_, px)) = self.coins.iter_mut().find(|(name, _)| name == coin) {
+            *px = mid;
+            self.events.send(WsEvent::AllMids).ok();
+        }
+    }
+
+    pub(crate) fn balance(&self, user: Address) -> Option<f64> {
+        self.accounts.get(&user).map(|account| account.balance)
+    }
+
+    /// Handle an `/exchange` body. `Err` is a malformed request, answered
+    /// with a 422 like the real API.
+    pub(crate) fn exchange(&mut self, body: &str) -> Result<Value, String> {
+        let request: ExchangeRequest = serde_json::from_str(body).map_err(|e| {
+            format!("Failed to deserialize the JSON body into the target type: {e}")
+        })?;
+        if let Ok(raw) = serde_json::from_str(body) {
+            self.requests.push(raw);
+        }
+
+        Ok(match self.execute(request) {
+            Ok(response) => json!({ "status": "ok", "response": response }),
+            Err(err) => json!({ "status": "err", "response": err }),
+        })
+    }
+
+    fn execute(&mut self, request: ExchangeRequest) -> Result<Value, String> {
+        let action: ActionKind = serde_json::from_value(request.action.clone())
+            .map_err(|e| format!("Failed to deserialize action: {e}"))?;
+        let signer = recover_signer(&request, &action)?;
+
+        let master = if self.accounts.contains_key(&signer) {
+            signer
+        } else if let Some(agent) = self.agents.get(&signer) {
+            if !action.is_l1_action() {
+                return Err(format!(
+                    "Agent {} cannot sign user-signed actions.",
+                    hex(signer)
+                ));
+            }
+            agent.master
+        } else {
+            return Err(format!(
+                "User or API Wallet {} does not exist.",
+                hex(signer)
+            ));
+        };
+        let user = match request.vault_address {
+            Some(vault) if self.accounts.contains_key(&vault) => vault,
+            Some(vault) => return Err(format!("Vault {} does not exist.", hex(vault))),
+            None => master,
+        };
+
+        if !self.nonces.insert((signer, request.nonce)) {
+            return Err(format!(
+                "Invalid nonce: {} was already used.",
+                request.nonce
+            ));
+        }
+
+        match action {
+            ActionKind::Order(bulk_order) => Ok(self.order(user, &bulk_order)),
+            ActionKind::Cancel(bulk_cancel) => {
+                let statuses: Vec<_> = bulk_cancel
+                    .cancels
+                    .iter()
+                    .map(|cancel| self.cancel(user, cancel.asset, |order| order.oid == cancel.oid))
+                    .collect();
+                Ok(json!({ "type": "cancel", "data": { "statuses": statuses } }))
+            }
+            ActionKind::CancelByCloid(bulk_cancel) => {
+                let statuses: Vec<_> = bulk_cancel
+                    .cancels
+                    .iter()
+                    .map(|cancel| {
+                        self.cancel(user, cancel.asset, |order| {
+                            order
+                                .cloid
+                                .as_ref()
+                                .is_some_and(|cloid| cloid.eq_ignore_ascii_case(&cancel.cloid))
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "type": "cancel", "data": { "statuses": statuses } }))
+            }
+            ActionKind::BatchModify(bulk_modify) => {
+                let statuses: Vec<_> = bulk_modify
+                    .modifies
+                    .iter()
+                    .map(|modify| {
+                        let Some(index) = self
+                            .orders
+                            .iter()
+                            .position(|order| order.oid == modify.oid && order.user == user)
+                        else {
+                            return json!({ "error": "Cannot modify canceled or filled order" });
+                        };
+                        self.orders.remove(index);
+                        self.place(user, &modify.order)
+                    })
+                    .collect();
+                Ok(json!({ "type": "order", "data": { "statuses": statuses } }))
+            }
+            ActionKind::UpdateLeverage(update) => {
+                if update.leverage == 0 || update.leverage > MAX_LEVERAGE {
+                    return Err("Invalid leverage value".to_string());
+                }
+                self.account(user)
+                    .leverage
+                    .insert(update.asset, update.leverage);
+                Ok(default_response())
+            }
+            ActionKind::UsdSend(usd_send) => {
+                let destination = usd_send
+                    .destination
+                    .parse::<Address>()
+                    .map_err(|_| "Invalid destination".to_string())?;
+                self.transfer(user, Some(destination), &usd_send.amount)
+            }
+            ActionKind::Withdraw3(withdraw) => self.transfer(user, None, &withdraw.amount),
+            ActionKind::ApproveAgent(approve_agent) => {
+                let name = approve_agent.agent_name.unwrap_or_default();
+                let (name, valid_until) = match name.split_once(" valid_until ") {
+                    Some((name, until)) => (name.to_string(), until.parse().ok()),
+                    None => (name, None),
+                };
+                self.agents
+                    .retain(|_, agent| agent.master != user || agent.name != name);
+                self.agents.insert(
+                    approve_agent.agent_address,
+                    Agent {
+                        master: user,
+                        name,
+                        valid_until,
+                    },
+                );
+                Ok(default_response())
+            }
+            ActionKind::ApproveBuilderFee(approve) => {
+                let rate = approve
+                    .max_fee_rate
+                    .trim_end_matches('%')
+                    .parse::<f64>()
+                    .map_err(|_| "Invalid max fee rate".to_string())?;
+                self.builder_fees
+                    .insert((user, approve.builder), (rate * 1000.0).round() as u64);
+                Ok(default_response())
+            }
+            ActionKind::MultiSig(_) => Err("multiSig is not supported by the mock".to_string()),
+            _ => Ok(default_response()),
+        }
+    }
+
+    fn account(&mut self, user: Address) -> &mut Account {
+        self.accounts.entry(user).or_default()
+    }
+
+    fn transfer(
+        &mut self,
+        user: Address,
+        destination: Option<Address>,
+        amount: &str,
+    ) -> Result<Value, String> {
+        let amount = amount
+            .parse::<f64>()
+            .map_err(|_| "Invalid amount".to_string())?;
+        if amount <= 0.0 || amount > self.withdrawable(user) {
+            return Err("Insufficient balance for transfer".to_string());
+        }
+
+        self.account(user).balance -= amount;
+        if let Some(destination) = destination {
+            self.account(destination).balance += amount;
+        }
+        Ok(default_response())
+    }
+
+    fn order(&mut self, user: Address, bulk_order: &BulkOrder) -> Value {
+        let statuses: Vec<_> = bulk_order
+            .orders
+            .iter()
+            .map(|order| self.place(user, order))
+            .collect();
+        json!({ "type": "order", "data": { "statuses": statuses } })
+    }
+
+    fn place(&mut self, user: Address, order: &OrderRequest) -> Value {
+        let asset = order.asset;
+        if (0..self.spot_pairs.len() as u32)
+            .map(spot_asset_id)
+            .any(|spot| spot == asset)
+        {
+            return json!({ "error": format!("Spot orders are not supported by the mock. asset={asset}") });
+        }
+        let Some(&(_, mid)) = self.coins.get(asset as usize) else {
+            return json!({ "error": format!("Invalid asset {asset}") });
+        };
+        let (Ok(px), Ok(sz)) = (order.limit_px.parse::<f64>(), order.sz.parse::<f64>()) else {
+            return json!({ "error": format!("Invalid price or size. asset={asset}") });
+        };
+        if px <= 0.0 || sz <= 0.0 {
+            return json!({ "error": format!("Order has zero size or price. asset={asset}") });
+        }
+
+        let leverage = self.leverage(user, asset);
+        if !order.reduce_only && px * sz / leverage as f64 > self.withdrawable(user) {
+            return json!({ "error": format!("Insufficient margin to place order. asset={asset}") });
+        }
+
+        let crosses = if order.is_buy { px >= mid } else { px <= mid };
+        let tif = match &order.order_type {
+            Order::Limit(limit) => limit.tif.as_str(),
+            Order::Trigger(_) => "Trigger",
+        };
+        let oid = self.next_oid;
+        let resting = RestingOrder {
+            oid,
+            user,
+            asset,
+            is_buy: order.is_buy,
+            limit_px: px,
+            sz,
+            cloid: order.cloid.clone(),
+            timestamp: now_timestamp_ms(),
+        };
+
+        match (tif, crosses) {
+            ("Ioc", false) => json!({
+                "error": format!(
+                    "Order could not immediately match against any resting orders. asset={asset}"
+                )
+            }),
+            ("Alo", true) => json!({
+                "error": format!(
+                    "Post only order would have immediately matched, bbo was {}. asset={asset}",
+                    wire(mid)
+                )
+            }),
+            ("Trigger", _) | (_, false) => {
+                self.next_oid += 1;
+                self.order_update(&resting, "open");
+                self.orders.push(resting);
+                match &order.cloid {
+                    Some(cloid) => json!({ "resting": { "oid": oid, "cloid": cloid } }),
+                    None => json!({ "resting": { "oid": oid } }),
+                }
+            }
+            _ => {
+                self.next_oid += 1;
+                self.fill(&resting, mid);
+                self.order_update(&resting, "filled");
+                json!({ "filled": { "totalSz": wire(sz), "avgPx": wire(mid), "oid": oid } })
+            }
+        }
+    }
+
+    fn cancel(
+        &mut self,
+        user: Address,
+        asset: u32,
+        matches: impl Fn(&RestingOrder) -> bool,
+    ) -> Value {
+        match self
+            .orders
+            .iter()
+            .position(|order| order.user == user && order.asset == asset && matches(order))
+        {
+            Some(index) => {
+                let order = self.orders.remove(index);
+                self.order_update(&order, "canceled");
+                json!("success")
+            }
+            None => json!({
+                "error": format!(
+                    "Order was never placed, already canceled, or filled. asset={asset}"
+                )
+            }),
+        }
+    }
+
+    fn fill(&mut self, order: &RestingOrder, px: f64) {
+        let (user, asset, sz) = (order.user, order.asset, order.sz);
+        let delta = if order.is_buy { sz } else { -sz };
+        let start_position = self.positions.get(&(user, asset)).map_or(0.0, |p| p.szi);
+        let position = self.positions.entry((user, asset)).or_insert(Position {
+            szi: 0.0,
+            entry_px: px,
+        });
+
+        let mut realized = 0.0;
+        if position.szi == 0.0 || position.szi.signum() == delta.signum() {
+            let szi = position.szi + delta;
+            position.entry_px = (position.entry_px * position.szi + px * delta) / szi;
+            position.szi = szi;
+        } else {
+            let closed = delta.abs().min(position.szi.abs());
+            realized = (px - position.entry_px) * closed * position.szi.signum();
+            position.szi += delta;
+            if position.szi.signum() == delta.signum() {
+                position.entry_px = px;
+            }
+        }
+
+        if position.szi.abs() < 1e-12 {
+            self.positions.remove(&(user, asset));
+        }
+        self.account(user).balance += realized;
+
+        let dir = match (start_position, delta > 0.0) {
+            (start, true) if start < 0.0 => "Close Short",
+            (_, true) => "Open Long",
+            (start, false) if start > 0.0 => "Close Long",
+            (_, false) => "Open Short",
+        };
+        let fill = json!({
+            "closedPnl": wire(realized),
+            "coin": self.coins[asset as usize].0,
+            "crossed": true,
+            "dir": dir,
+            "hash": format!("{:#066x}", order.oid),
+            "oid": order.oid,
+            "px": wire(px),
+            "side": if order.is_buy { "B" } else { "A" },
+            "startPosition": wire(start_position),
+            "sz": wire(sz),
+            "time": order.timestamp,
+            "fee": "0",
+            "tid": order.oid,
+            "feeToken": "USDC",
+            "twapId": null,
+        });
+        self.fills.push((user, fill));
+    }
+
+    fn order_update(&self, order: &RestingOrder, status: &str) {
+        let update = json!({
+            "order": self.open_order(order),
+            "status": status,
+            "statusTimestamp": now_timestamp_ms(),
+        });
+        self.events
+            .send(WsEvent::OrderUpdate {
+                user: order.user,
+                update,
+            })
+            .ok();
+    }
+
+    fn open_order(&self, order: &RestingOrder) -> Value {
+        json!({
+            "coin": self.coins[order.asset as usize].0,
+            "limitPx": wire(order.limit_px),
+            "oid": order.oid,
+            "side": if order.is_buy { "B" } else { "A" },
+            "sz": wire(order.sz),
+            "timestamp": order.timestamp,
+            "cloid": order.cloid,
+        })
+    }
+
+    fn leverage(&self, user: Address, asset: u32) -> u32 {
+        self.accounts
+            .get(&user)
+            .and_then(|account| account.leverage.get(&asset).copied())
+            .unwrap_or(DEFAULT_LEVERAGE)
+    }
+
+    fn unrealized_pnl(&self, user: Address) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((owner, _), _)| *owner == user)
+            .map(|(&(_, asset), position)| {
+                (self.coins[asset as usize].1 - position.entry_px) * position.szi
+            })
+            .sum()
+    }
+
+    fn margin_used(&self, user: Address) -> f64 {
+        let positions: f64 = self
+            .positions
+            .iter()
+            .filter(|((owner, _), _)| *owner == user)
+            .map(|(&(_, asset), position)| {
+                position.szi.abs() * self.coins[asset as usize].1
+                    / self.leverage(user, asset) as f64
+            })
+            .sum();
+        let orders: f64 = self
+            .orders
+            .iter()
+            .filter(|order| order.user == user)
+            .map(|order| order.limit_px * order.sz / self.leverage(user, order.asset) as f64)
+            .sum();
+        positions + orders
+    }
+
+    fn withdrawable(&self, user: Address) -> f64 {
+        let balance = self.balance(user).unwrap_or_default();
+        (balance + self.unrealized_pnl(user) - self.margin_used(user)).max(0.0)
+    }
+
+    /// Handle an `/info` body.
+    pub(crate) fn info(&self, body: &str) -> Result<Value, String> {
+        let request: Value = serde_json::from_str(body)
+            .map_err(|e| format!("Failed to deserialize the JSON body: {e}"))?;
+        let user = || {
+            request["user"]
+                .as_str()
+                .and_then(|user| user.parse::<Address>().ok())
+                .ok_or_else(|| "Failed to deserialize the JSON body: missing user".to_string())
+        };
+
+        match request["type"].as_str().unwrap_or_default() {
+            "allMids" => Ok(self.mids()),
+            "meta" => Ok(json!({
+                "universe": self
+                    .coins
+                    .iter()
+                    .map(|(coin, _)| json!({
+                        "name": coin,
+                        "szDecimals": 4,
+                        "maxLeverage": MAX_LEVERAGE,
+                    }))
+                    .collect::<Vec<_>>(),
+                "marginTables": [],
+            })),
+            "perpDexs" => Ok(json!([null])),
+            "spotMeta" => Ok(self.spot_meta()),
+            "openOrders" => {
+                let user = user()?;
+                Ok(self
+                    .orders
+                    .iter()
+                    .filter(|order| order.user == user)
+                    .map(|order| self.open_order(order))
+                    .collect())
+            }
+            "userFills" => {
+                let user = user()?;
+                Ok(self
+                    .fills
+                    .iter()
+                    .rev()
+                    .filter(|(owner, _)| *owner == user)
+                    .map(|(_, fill)| fill.clone())
+                    .collect())
+            }
+            "l2Book" => {
+                let coin = request["coin"].as_str().unwrap_or_default();
+                let asset = self
+                    .coins
+                    .iter()
+                    .position(|(name, _)| name == coin)
+                    .ok_or_else(|| {
+                        format!("Failed to deserialize the JSON body: unknown coin {coin}")
+                    })? as u32;
+                Ok(json!({
+                    "coin": coin,
+                    "levels": [self.levels(asset, true), self.levels(asset, false)],
+                    "time": now_timestamp_ms(),
+                }))
+            }
+            "clearinghouseState" => Ok(self.user_state(user()?)),
+            "extraAgents" => {
+                let user = user()?;
+                Ok(self
+                    .agents
+                    .iter()
+                    .filter(|(_, agent)| agent.master == user)
+                    .map(|(address, agent)| {
+                        json!({
+                            "name": agent.name,
+                            "address": hex(*address),
+                            "validUntil": agent.valid_until,
+                        })
+                    })
+                    .collect())
+            }
+            "maxBuilderFee" => {
+                let builder = request["builder"]
+                    .as_str()
+                    .and_then(|builder| builder.parse::<Address>().ok())
+                    .ok_or("Failed to deserialize the JSON body: missing builder")?;
+                Ok(json!(
+                    self.builder_fees
+                        .get(&(user()?, builder))
+                        .copied()
+                        .unwrap_or_default()
+                ))
+            }
+            other => Err(format!(
+                "Failed to deserialize the JSON body: unknown type {other}"
+            )),
+        }
+    }
+
+    /// Book side of `asset` aggregated by price, best price first.
+    fn levels(&self, asset: u32, is_buy: bool) -> Vec<Value> {
+        let mut levels: Vec<(f64, f64, u64)> = vec![];
+        for order in self
+            .orders
+            .iter()
+            .filter(|order| order.asset == asset && order.is_buy == is_buy)
+        {
+            match levels.iter_mut().find(|(px, _, _)| *px == order.limit_px) {
+                Some(level) => {
+                    level.1 += order.sz;
+                    level.2 += 1;
+                }
+                None => levels.push((order.limit_px, order.sz, 1)),
+            }
+        }
+        levels.sort_by(|a, b| {
+            let ordering = a.0.total_cmp(&b.0);
+            if is_buy { ordering.reverse() } else { ordering }
+        });
+
+        levels
+            .into_iter()
+            .map(|(px, sz, n)| json!({ "px": wire(px), "sz": wire(sz), "n": n }))
+            .collect()
+    }
+
+    fn user_state(&self, user: Address) -> Value {
+        let balance = self.balance(user).unwrap_or_default();
+        let account_value = balance + self.unrealized_pnl(user);
+        let margin_used = self.margin_used(user);
+
+        let mut total_ntl_pos = 0.0;
+        let asset_positions: Vec<_> = self
+            .positions
+            .iter()
+            .filter(|((owner, _), _)| *owner == user)
+            .map(|(&(_, asset), position)| {
+                let (coin, mid) = &self.coins[asset as usize];
+                let leverage = self.leverage(user, asset);
+                let position_value = position.szi.abs() * mid;
+                let unrealized_pnl = (mid - position.entry_px) * position.szi;
+                let position_margin = position_value / leverage as f64;
+                total_ntl_pos += position_value;
+                json!({
+                    "type": "oneWay",
+                    "position": {
+                        "coin": coin,
+                        "entryPx": wire(position.entry_px),
+                        "leverage": { "type": "cross", "value": leverage },
+                        "liquidationPx": null,
+                        "marginUsed": wire(position_margin),
+                        "positionValue": wire(position_value),
+                        "returnOnEquity": wire(unrealized_pnl / position_margin),
+                        "szi": wire(position.szi),
+                        "unrealizedPnl": wire(unrealized_pnl),
+                        "maxLeverage": MAX_LEVERAGE,
+                        "cumFunding": { "allTime": "0", "sinceOpen": "0", "sinceChange": "0" },
+                    },
+                })
+            })
+            .collect();
+
+        let summary = json!({
+            "accountValue": wire(account_value),
+            "totalMarginUsed": wire(margin_used),
+            "totalNtlPos": wire(total_ntl_pos),
+            "totalRawUsd": wire(balance),
+        });
+        json!({
+            "assetPositions": asset_positions,
+            "crossMarginSummary": summary,
+            "marginSummary": summary,
+            "withdrawable": wire(self.withdrawable(user)),
+            "time": now_timestamp_ms(),
+        })
+    }
+}
+
+fn default_response() -> Value {
+    json!({ "type": "default" })
+}
+
+/// Signer of a request, recovered the way the exchange does it: from the
+/// `Agent` connection id for L1 actions, or from the action's EIP-712 hash.
+/// The mock serves testnet, so L1 actions use source "b".
+fn recover_signer(request: &ExchangeRequest, action: &ActionKind) -> Result<Address, String> {
+    let hash = if action.is_l1_action() {
+        let connection_id = l1_action_hash(&request.action, request.nonce, request.vault_address)
+            .map_err(|e| e.to_string())?;
+        l1_action_signing_hash(connection_id, false)
+    } else {
+        action.extract_eip712_hash().map_err(|e| e.to_string())?
+    };
+
+    let parity = match request.signature.v {
+        27 => false,
+        28 => true,
+        v => return Err(format!("Invalid signature v {v}")),
+    };
+    Signature::new(request.signature.r, request.signature.s, parity)
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| format!("Invalid signature: {e}"))
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use alloy::primitives::Address;
+use axum::{
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::Response,
+};
+use serde_json::{Value, json};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::testing::{SharedState, state::WsEvent};
+
+pub(crate) async fn upgrade(ws: WebSocketUpgrade, State(state): State<SharedState>) -> Response {
+    ws.on_upgrade(move |socket| serve(socket, state))
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    all_mids: bool,
+    order_updates: HashSet<Address>,
+}
+
+async fn serve(mut socket: WebSocket, state: SharedState) {
+    let mut events = state.lock().unwrap().subscribe();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let replies = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => handle(&state, &mut subscriptions, &text),
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) => push(&state, &subscriptions, event),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            },
+        };
+
+        for reply in replies {
+            if socket
+                .send(Message::Text(reply.to_string().into()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+fn handle(state: &SharedState, subscriptions: &mut Subscriptions, text: &str) -> Vec<Value> {
+    let Ok(request) = serde_json::from_str::<Value>(text) else {
+        return vec![json!({ "channel": "error", "data": format!("Invalid message: {text}") })];
+    };
+
+    match request["method"].as_str().unwrap_or_default() {
+        "ping" => vec![json!({ "channel": "pong" })],
+        method @ ("subscribe" | "unsubscribe") => {
+            let subscription = &request["subscription"];
+            let subscribe = method == "subscribe";
+            let mut replies = vec![json!({
+                "channel": "subscriptionResponse",
+                "data": { "method": method, "subscription": subscription },
+            })];
+
+            match subscription["type"].as_str().unwrap_or_default() {
+                "allMids" => {
+                    subscriptions.all_mids = subscribe;
+                    if subscribe {
+                        replies.push(all_mids(state));
+                    }
+                }
+                "orderUpdates" => {
+                    let Some(user) = subscription["user"]
+                        .as_str()
+                        .and_then(|user| user.parse().ok())
+                    else {
+                        return vec![json!({ "channel": "error", "data": "Invalid user" })];
+                    };
+                    if subscribe {
+                        subscriptions.order_updates.insert(user);
+                    } else {
+                        subscriptions.order_updates.remove(&user);
+                    }
+                }
+                other => {
+                    return vec![json!({
+                        "channel": "error",
+                        "data": format!("Unsupported subscription {other}"),
+                    })];
+                }
+            }
+            replies
+        }
+        "post" => {
+            let kind = request["request"]["type"].as_str().unwrap_or_default();
+            let payload = request["request"]["payload"].to_string();
+            let response = match kind {
+                "info" => state.lock().unwrap().info(&payload).map(
+                    |data| json!({ "type": request["request"]["payload"]["type"], "data": data }),
+                ),
+                "action" => state.lock().unwrap().exchange(&payload),
+                other => Err(format!("Unsupported post type {other}")),
+            };
+            let response = match response {
+                Ok(payload) => json!({ "type": kind, "payload": payload }),
+                Err(err) => json!({ "type": "error", "payload": err }),
+            };
+            vec![json!({
+                "channel": "post",
+                "data": { "id": request["id"], "response": response },
+            })]
+        }
+        other => vec![json!({ "channel": "error", "data": format!("Unsupported method {other}") })],
+    }
+}
+
+fn push(state: &SharedState, subscriptions: &Subscriptions, event: WsEvent) -> Vec<Value> {
+    match event {
+        WsEvent::AllMids if subscriptions.all_mids => vec![all_mids(state)],
+        WsEvent::OrderUpdate { user, update } if subscriptions.order_updates.contains(&user) => {
+            vec![json!({ "channel": "orderUpdates", "data": [update] })]
+        }
+        _ => vec![],
+    }
+}
+
+fn all_mids(state: &SharedState) -> Value {
+    json!({ "channel": "allMids", "data": { "mids": state.lock().unwrap().mids() } })
+}
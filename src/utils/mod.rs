@@ -9,13 +9,18 @@ use crate::consts::*;
 
 mod signing;
 
-pub use signing::sign_l1_action;
+pub use signing::{l1_action_signing_hash, sign_l1_action};
 
-fn now_timestamp_ms() -> u64 {
+pub(crate) fn now_timestamp_ms() -> u64 {
     let now = Utc::now();
     now.timestamp_millis() as u64
 }
 
+/// `hyperliquidChain` of user-signed actions on mainnet or testnet.
+pub(crate) fn hyperliquid_chain(is_mainnet: bool) -> &'static str {
+    if is_mainnet { "Mainnet" } else { "Testnet" }
+}
+
 pub fn next_nonce() -> u64 {
     let nonce = CUR_NONCE.fetch_add(1, Ordering::Relaxed);
     let now_ms = now_timestamp_ms();
@@ -56,6 +61,21 @@ pub fn uuid_to_hex_string(uuid: Uuid) -> String {
     format!("0x{hex_string}")
 }
 
+/// Asset id of the `asset_index`-th asset of a perp dex. The main dex has
+/// index 0 and keeps plain universe indices.
+pub fn perp_asset_id(dex_index: u32, asset_index: u32) -> u32 {
+    if dex_index == 0 {
+        asset_index
+    } else {
+        PERP_DEX_ASSET_OFFSET + dex_index * PERP_DEX_ASSET_STRIDE + asset_index
+    }
+}
+
+/// Asset id of the spot pair with universe index `index`.
+pub fn spot_asset_id(index: u32) -> u32 {
+    SPOT_ASSET_OFFSET + index
+}
+
 pub fn truncate_float(float: f64, decimals: u32, round_up: bool) -> f64 {
     let pow10 = 10i64.pow(decimals) as f64;
     let mut float = (float * pow10) as u64;
@@ -82,6 +102,20 @@ lazy_static! {
 mod tests {
     use super::*;
 
+    #[test]
+    fn perp_asset_id_test() {
+        assert_eq!(perp_asset_id(0, 0), 0);
+        assert_eq!(perp_asset_id(0, 159), 159);
+        assert_eq!(perp_asset_id(1, 0), 110000);
+        assert_eq!(perp_asset_id(2, 3), 120003);
+    }
+
+    #[test]
+    fn spot_asset_id_test() {
+        assert_eq!(spot_asset_id(0), 10000);
+        assert_eq!(spot_asset_id(107), 10107);
+    }
+
     #[test]
     fn float_to_string_for_hashing_test() {
         assert_eq!(float_to_string_for_hashing(0.), "0".to_string());
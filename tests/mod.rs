@@ -0,0 +1,542 @@
+use std::time::Duration;
+
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use futures_util::{SinkExt, StreamExt};
+use hl_rs::{
+    Error, ExchangeClient, Result, SIGNATURE_CHAIN_ID,
+    exchange::{
+        ActionKind,
+        builder::{self, BuildAction},
+        requests::{
+            ApproveAgent, BulkCancel, BulkOrder, CancelRequest, ClientLimit, ClientOrder,
+            ClientOrderRequest, UsdSend, Withdraw3,
+        },
+        responses::{ExchangeDataStatus, ExchangeResponseData, ExchangeResponseStatus},
+    },
+    http::Cassette,
+    signer::{Policy, RemoteSigner, SignerConfig, SignerServer},
+    testing::{MockConfig, MockServer},
+};
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn start(accounts: &[&PrivateKeySigner]) -> Result<MockServer> {
+    MockServer::start(MockConfig {
+        accounts: accounts
+            .iter()
+            .map(|wallet| (wallet.address(), 10_000.0))
+            .collect(),
+        ..Default::default()
+    })
+    .await
+}
+
+fn order(
+    client: &ExchangeClient,
+    coin: &str,
+    is_buy: bool,
+    limit_px: f64,
+    tif: &str,
+) -> ActionKind {
+    let order = ClientOrderRequest {
+        asset: coin.to_string(),
+        is_buy,
+        reduce_only: false,
+        limit_px,
+        sz: 0.1,
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: tif.to_string(),
+        }),
+    };
+
+    ActionKind::Order(BulkOrder {
+        orders: vec![order.convert(client.coin_to_asset()).unwrap()],
+        grouping: "na".to_string(),
+        builder: None,
+    })
+}
+
+fn statuses(status: ExchangeResponseStatus) -> Vec<ExchangeDataStatus> {
+    match status {
+        ExchangeResponseStatus::Ok(response) => match response.data {
+            Some(ExchangeResponseData::Statuses(data)) => data.statuses,
+            data => panic!("unexpected response data {data:?}"),
+        },
+        ExchangeResponseStatus::Err(err) => panic!("unexpected error {err}"),
+    }
+}
+
+fn error(status: ExchangeResponseStatus) -> String {
+    match status {
+        ExchangeResponseStatus::Err(err) => err,
+        ExchangeResponseStatus::Ok(response) => panic!("unexpected success {response:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_mock_orders() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+    let info_client = server.info_client()?;
+
+    let status = order(&client, "ETH", true, 2_900.0, "Gtc")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await?;
+    let oid = match &statuses(status)[..] {
+        [ExchangeDataStatus::Resting(resting)] => resting.oid,
+        statuses => panic!("unexpected statuses {statuses:?}"),
+    };
+
+    let open_orders = info_client.open_orders(wallet.address()).await?;
+    assert_eq!(open_orders.len(), 1);
+    assert_eq!(open_orders[0].oid, oid);
+    assert_eq!(open_orders[0].coin, "ETH");
+    assert_eq!(open_orders[0].limit_px, "2900");
+
+    let status = order(&client, "ETH", true, 3_100.0, "Ioc")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await?;
+    match &statuses(status)[..] {
+        [ExchangeDataStatus::Filled(filled)] => {
+            assert_eq!(filled.total_sz, "0.1");
+            assert_eq!(filled.avg_px, "3000");
+        }
+        statuses => panic!("unexpected statuses {statuses:?}"),
+    }
+    let user_state = info_client.user_state(wallet.address()).await?;
+    assert_eq!(user_state.asset_positions.len(), 1);
+    assert_eq!(user_state.asset_positions[0].position.szi, "0.1");
+
+    let cancel = ActionKind::Cancel(BulkCancel {
+        cancels: vec![CancelRequest { asset: 1, oid }],
+    });
+    let status = cancel.clone().build(&client)?.sign(&wallet)?.send().await?;
+    assert!(matches!(
+        &statuses(status)[..],
+        [ExchangeDataStatus::Success]
+    ));
+    assert!(info_client.open_orders(wallet.address()).await?.is_empty());
+
+    let status = cancel.build(&client)?.sign(&wallet)?.send().await?;
+    match &statuses(status)[..] {
+        [ExchangeDataStatus::Error(err)] => assert!(err.contains("never placed")),
+        statuses => panic!("unexpected statuses {statuses:?}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_rejects_unknown_signer() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let stranger = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+
+    let status = order(&client, "BTC", true, 90_000.0, "Gtc")
+        .build(&client)?
+        .sign(&stranger)?
+        .send()
+        .await?;
+    assert!(error(status).contains("does not exist"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_noop_invalidates_nonce() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+
+    let pre_signed = order(&client, "SOL", false, 200.0, "Gtc")
+        .build(&client)?
+        .sign(&wallet)?;
+    builder::sign_noop(&client, &wallet, pre_signed.nonce)?
+        .send()
+        .await?;
+
+    assert!(error(pre_signed.send().await?).contains("already used"));
+    assert_eq!(server.exchange_requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_usd_send() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let destination = Address::repeat_byte(0x42);
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+
+    let usd_send = |amount: &str| {
+        ActionKind::UsdSend(UsdSend {
+            signature_chain_id: 421614,
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: destination.to_string(),
+            amount: amount.to_string(),
+            time: hl_rs::utils::next_nonce(),
+        })
+    };
+
+    usd_send("250")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await?;
+    assert_eq!(server.balance(wallet.address()), Some(9_750.0));
+    assert_eq!(server.balance(destination), Some(250.0));
+
+    let status = usd_send("20000")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await?;
+    assert!(error(status).contains("Insufficient balance"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_agent() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let agent = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+    let info_client = server.info_client()?;
+
+    ActionKind::ApproveAgent(ApproveAgent {
+        signature_chain_id: 421614,
+        hyperliquid_chain: "Testnet".to_string(),
+        agent_address: agent.address(),
+        agent_name: Some("bot valid_until 1900000000000".to_string()),
+        nonce: hl_rs::utils::next_nonce(),
+    })
+    .build(&client)?
+    .sign(&wallet)?
+    .send()
+    .await?;
+
+    let agents = info_client.extra_agents(wallet.address()).await?;
+    assert_eq!(agents.len(), 1);
+    assert_eq!(agents[0].name, "bot");
+    assert_eq!(agents[0].address, agent.address());
+    assert_eq!(agents[0].valid_until, Some(1_900_000_000_000));
+
+    order(&client, "ETH", true, 2_000.0, "Gtc")
+        .build(&client)?
+        .sign(&agent)?
+        .send()
+        .await?;
+    assert_eq!(info_client.open_orders(wallet.address()).await?.len(), 1);
+    Ok(())
+}
+
+async fn next(socket: &mut Socket) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("no websocket message")
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_mock_ws() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+
+    let (mut socket, _) = connect_async(server.ws_url()).await.unwrap();
+    for subscription in [
+        json!({ "type": "allMids" }),
+        json!({ "type": "orderUpdates", "user": wallet.address() }),
+    ] {
+        let request = json!({ "method": "subscribe", "subscription": subscription });
+        socket
+            .send(Message::Text(request.to_string().into()))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(next(&mut socket).await["channel"], "subscriptionResponse");
+    let mids = next(&mut socket).await;
+    assert_eq!(mids["channel"], "allMids");
+    assert_eq!(mids["data"]["mids"]["BTC"], "100000");
+    assert_eq!(next(&mut socket).await["channel"], "subscriptionResponse");
+
+    server.set_mid("BTC", 101_000.0);
+    let mids = next(&mut socket).await;
+    assert_eq!(mids["data"]["mids"]["BTC"], "101000");
+
+    order(&client, "BTC", true, 95_000.0, "Gtc")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await?;
+    let update = next(&mut socket).await;
+    assert_eq!(update["channel"], "orderUpdates");
+    assert_eq!(update["data"][0]["status"], "open");
+    assert_eq!(update["data"][0]["order"]["limitPx"], "95000");
+
+    let post = json!({ "method": "post", "id": 7, "request": { "type": "info", "payload": { "type": "openOrders", "user": wallet.address() } } });
+    socket
+        .send(Message::Text(post.to_string().into()))
+        .await
+        .unwrap();
+    let response = next(&mut socket).await;
+    assert_eq!(response["channel"], "post");
+    assert_eq!(response["data"]["id"], 7);
+    assert_eq!(
+        response["data"]["response"]["payload"]["data"][0]["coin"],
+        "BTC"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_record_and_replay() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let path = std::env::temp_dir().join(format!(
+        "hl-rs-cassette-{}.json",
+        hl_rs::utils::next_nonce()
+    ));
+
+    let server = start(&[&wallet]).await?;
+    let mut client = server.exchange_client(None)?;
+    let mut info_client = server.info_client()?;
+    let cassette = Cassette::record(&path);
+    client.set_cassette(Some(cassette.clone()));
+    info_client.set_cassette(Some(cassette.clone()));
+
+    let recorded = statuses(
+        order(&client, "ETH", true, 2_900.0, "Gtc")
+            .build(&client)?
+            .sign(&wallet)?
+            .send()
+            .await?,
+    );
+    info_client.open_orders(wallet.address()).await?;
+    assert_eq!(cassette.interactions().len(), 2);
+    drop(server);
+
+    let cassette = Cassette::replay(&path)?;
+    client.set_cassette(Some(cassette.clone()));
+    info_client.set_cassette(Some(cassette));
+    std::fs::remove_file(path).ok();
+
+    // A fresh nonce and signature still match the recorded order.
+    let replayed = statuses(
+        order(&client, "ETH", true, 2_900.0, "Gtc")
+            .build(&client)?
+            .sign(&wallet)?
+            .send()
+            .await?,
+    );
+    assert_eq!(format!("{replayed:?}"), format!("{recorded:?}"));
+    assert_eq!(info_client.open_orders(wallet.address()).await?.len(), 1);
+
+    let other = order(&client, "ETH", true, 2_800.0, "Gtc")
+        .build(&client)?
+        .sign(&wallet)?
+        .send()
+        .await;
+    assert!(other.is_err());
+    Ok(())
+}
+
+// The CLI blocks on the binary, so the mock runs on other worker threads.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let server = MockServer::start(MockConfig {
+        accounts: vec![(wallet.address(), 10_000.0)],
+        ..Default::default()
+    })
+    .await?;
+
+    let hl = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_hl"))
+            .args(["--network", "localhost"])
+            .args(args)
+            .env("HL_API_URL", server.url())
+            .env("HL_PRIVATE_KEY", wallet.to_bytes().to_string())
+            .env_remove("HL_VAULT_ADDRESS")
+            .env_remove("HL_KEYSTORE")
+            .env_remove("HL_MNEMONIC")
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    };
+
+    let (success, stdout) = hl(&["order", "ETH", "buy", "0.1", "2900"]);
+    assert!(success);
+    assert!(stdout.starts_with("resting: oid "));
+
+    let (success, stdout) = hl(&["--json", "orders"]);
+    assert!(success);
+    let orders: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(orders[0]["coin"], "ETH");
+    let oid = orders[0]["oid"].to_string();
+
+    let (success, stdout) = hl(&["cancel", "ETH", &oid]);
+    assert!(success);
+    assert_eq!(stdout, "ok\n");
+
+    let (success, _) = hl(&["cancel", "ETH", &oid]);
+    assert!(!success);
+
+    // Spot pairs are numbered from 10000. The mock refuses spot orders, so
+    // check what was sent.
+    let (success, _) = hl(&["order", "PURR/USDC", "buy", "10", "0.2"]);
+    assert!(!success);
+    let request = server.exchange_requests().pop().unwrap();
+    assert_eq!(request["action"]["orders"][0]["a"], 10000);
+
+    let destination = PrivateKeySigner::random().address();
+    let (success, stdout) = hl(&["transfer", &destination.to_string(), "5"]);
+    assert!(success);
+    assert_eq!(stdout, "ok: default\n");
+    let request = server.exchange_requests().pop().unwrap();
+    assert_eq!(
+        request["action"]["destination"],
+        destination.to_string().to_lowercase()
+    );
+    assert_eq!(server.balance(destination), Some(5.0));
+
+    // Sign offline against a saved asset map, then broadcast.
+    let dir = std::env::temp_dir().join(format!("hl-rs-cli-{}", hl_rs::utils::next_nonce()));
+    std::fs::create_dir(&dir).unwrap();
+    let assets = dir.join("assets.json");
+    let signed = dir.join("order.json");
+    let (success, stdout) = hl(&["assets"]);
+    assert!(success);
+    std::fs::write(&assets, stdout).unwrap();
+
+    let (success, stdout) = hl(&[
+        "--assets",
+        assets.to_str().unwrap(),
+        "--output",
+        signed.to_str().unwrap(),
+        "order",
+        "BTC",
+        "buy",
+        "0.1",
+        "95000",
+    ]);
+    assert!(success);
+    assert!(stdout.is_empty());
+    assert!(hl(&["orders"]).1.starts_with("no open orders"));
+
+    let (success, stdout) = hl(&["broadcast", signed.to_str().unwrap()]);
+    assert!(success);
+    assert!(stdout.contains(&format!("order signed by {}", wallet.address())));
+    assert!(stdout.contains("resting: oid "));
+
+    // The nonce is spent, so the same file cannot be sent twice.
+    assert!(!hl(&["broadcast", signed.to_str().unwrap()]).0);
+
+    // Transfers name no coin, so signing one needs no API at all.
+    let signed = dir.join("transfer.json");
+    let (success, stdout) = hl(&[
+        "--base-url",
+        "http://127.0.0.1:9",
+        "--output",
+        signed.to_str().unwrap(),
+        "transfer",
+        &destination.to_string(),
+        "5",
+    ]);
+    assert!(success);
+    assert!(stdout.is_empty());
+    assert!(hl(&["broadcast", signed.to_str().unwrap()]).0);
+    assert_eq!(server.balance(destination), Some(10.0));
+    std::fs::remove_dir_all(dir).ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_signer() -> Result<()> {
+    let wallet = PrivateKeySigner::random();
+    let server = start(&[&wallet]).await?;
+    let client = server.exchange_client(None)?;
+    let policy: Policy = serde_json::from_value(json!({
+        "allowedActions": ["order", "withdraw3"],
+        "maxNotional": 1000.0,
+        "withdrawalAllowlist": [wallet.address()],
+    }))
+    .unwrap();
+    let signer_server = SignerServer::start(SignerConfig {
+        wallet: wallet.clone(),
+        policy,
+        is_mainnet: false,
+        token: Some("secret".to_string()),
+        addr: "127.0.0.1:0".parse().unwrap(),
+    })
+    .await?;
+    let signer = RemoteSigner::new(signer_server.url(), Some("secret".to_string()));
+    assert_eq!(signer.address().await?, wallet.address());
+
+    let status = order(&client, "ETH", true, 2_900.0, "Gtc")
+        .build(&client)?
+        .sign_with(&signer)
+        .await?
+        .send()
+        .await?;
+    assert!(matches!(
+        &statuses(status)[..],
+        [ExchangeDataStatus::Resting(_)]
+    ));
+
+    let err = order(&client, "BTC", true, 90_000.0, "Gtc")
+        .build(&client)?
+        .sign_with(&signer)
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::PolicyViolation(message) if message.contains("above 1000")));
+
+    // The signer is on testnet, so it must not sign a mainnet withdrawal.
+    let withdraw = |hyperliquid_chain: &str| {
+        ActionKind::Withdraw3(Withdraw3 {
+            signature_chain_id: SIGNATURE_CHAIN_ID,
+            hyperliquid_chain: hyperliquid_chain.to_string(),
+            destination: wallet.address().to_string(),
+            amount: "1".to_string(),
+            time: 1_700_000_000_000,
+        })
+    };
+    assert!(
+        withdraw("Testnet")
+            .build(&client)?
+            .sign_with(&signer)
+            .await
+            .is_ok()
+    );
+    let err = withdraw("Mainnet")
+        .build(&client)?
+        .sign_with(&signer)
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::RemoteSigner(message) if message.contains("on Testnet")));
+
+    let stranger = RemoteSigner::new(signer_server.url(), None);
+    assert!(matches!(
+        stranger.address().await,
+        Err(Error::RemoteSigner(_))
+    ));
+    Ok(())
+}